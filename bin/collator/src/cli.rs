@@ -62,6 +62,10 @@ pub enum Subcommand {
     #[structopt(name = "export-genesis-wasm")]
     ExportGenesisWasm(ExportGenesisWasmCommand),
 
+    /// Sign runtime call by Ethereum key and print the custom signatures extrinsic.
+    #[structopt(name = "sign-eth-call")]
+    SignEthCall(SignEthCallCommand),
+
     /// The custom benchmark subcommmand benchmarking runtime pallets.
     #[cfg(feature = "runtime-benchmarks")]
     #[structopt(name = "benchmark", about = "Benchmark runtime pallets.")]
@@ -110,6 +114,46 @@ pub struct ExportGenesisWasmCommand {
     pub chain: Option<String>,
 }
 
+/// Command for signing runtime call by Ethereum key.
+#[derive(Debug, StructOpt)]
+pub struct SignEthCallCommand {
+    /// SCALE-encoded runtime call in hex.
+    #[structopt(long)]
+    pub call: String,
+
+    /// Secret URI of the secp256k1 key, e.g. hex seed or `//Alice`.
+    #[structopt(long)]
+    pub suri: String,
+
+    /// Nonce of the signer account.
+    #[structopt(long)]
+    pub nonce: u32,
+
+    /// Number of the recent block that mortal transaction is born at.
+    ///
+    /// Transaction is immortal if unspecified.
+    #[structopt(long, requires = "block-hash")]
+    pub block_number: Option<u64>,
+
+    /// Hash of the block given by `--block-number` in hex.
+    #[structopt(long, requires = "block-number")]
+    pub block_hash: Option<String>,
+
+    /// Number of blocks mortal transaction is valid for, rounded to power of two.
+    #[structopt(long, default_value = "64")]
+    pub period: u64,
+
+    /// Id of the parachain, it's a part of the genesis of dev chains.
+    ///
+    /// Default: 2007 (shiden)
+    #[structopt(long, default_value = "2007")]
+    pub parachain_id: u32,
+
+    /// The name of the chain for that the call is signed.
+    #[structopt(long)]
+    pub chain: Option<String>,
+}
+
 #[allow(missing_docs)]
 #[derive(Debug, StructOpt)]
 pub struct RunCmd {
@@ -1,6 +1,6 @@
 //! Astar collator CLI handlers.
 use crate::{
-    cli::{Cli, RelayChainCli, Subcommand},
+    cli::{Cli, RelayChainCli, SignEthCallCommand, Subcommand},
     local::{self, development_config},
     parachain::{
         self, astar, chain_spec, shibuya, shiden, start_astar_node, start_shibuya_node,
@@ -8,7 +8,7 @@ use crate::{
     },
     primitives::Block,
 };
-use codec::Encode;
+use codec::{Decode, Encode};
 use cumulus_client_service::genesis::generate_genesis_block;
 use cumulus_primitives_core::ParaId;
 use log::info;
@@ -21,8 +21,18 @@ use sc_service::{
     config::{BasePath, PrometheusConfig},
     PartialComponents,
 };
-use sp_core::hexdisplay::HexDisplay;
-use sp_runtime::traits::Block as BlockT;
+use pallet_custom_signatures::{
+    ethereum::EthereumSignature,
+    evm::AddressMapping,
+    multi::AnySignature,
+    signer::{self, Mortality},
+    PayloadVersion,
+};
+use sp_core::{ecdsa, hexdisplay::HexDisplay, Get, Pair, H256};
+use sp_runtime::{
+    generic::Era,
+    traits::{Block as BlockT, Extrinsic as ExtrinsicT, Header as HeaderT},
+};
 use std::{io::Write, net::SocketAddr};
 
 trait IdentifyChain {
@@ -205,6 +215,74 @@ fn extract_genesis_wasm(chain_spec: &Box<dyn sc_service::ChainSpec>) -> Result<V
         .ok_or_else(|| "Could not find wasm file in genesis state!".into())
 }
 
+/// Replay protection fields of the signed call, immortal unless birth block is given.
+fn eth_call_mortality(params: &SignEthCallCommand, genesis_hash: H256) -> Result<Mortality> {
+    let (number, hash) = match (params.block_number, &params.block_hash) {
+        (Some(number), Some(hash)) => (number, hash),
+        _ => {
+            return Ok(Mortality {
+                era: Era::Immortal,
+                genesis_hash,
+                era_block_hash: genesis_hash,
+            })
+        }
+    };
+
+    let era = Era::mortal(params.period, number);
+    // Long period is quantized, so the era could start before the given block
+    if era.birth(number) != number {
+        return Err("Period is too long for the given block".into());
+    }
+    let era_block_hash = sp_core::bytes::from_hex(hash)
+        .ok()
+        .filter(|hash| hash.len() == 32)
+        .map(|hash| H256::from_slice(&hash))
+        .ok_or("Invalid block hash")?;
+    Ok(Mortality {
+        era,
+        genesis_hash,
+        era_block_hash,
+    })
+}
+
+/// Build unsigned extrinsic of `EthCall::versioned_call` signed by Ethereum key.
+fn eth_call_extrinsic<Runtime, Extrinsic>(
+    params: &SignEthCallCommand,
+    mortality: &Mortality,
+    signer: impl FnOnce(&ecdsa::Pair) -> Runtime::AccountId,
+    signature: impl FnOnce(&ecdsa::Pair, &[u8]) -> Vec<u8>,
+) -> Result<Vec<u8>>
+where
+    Runtime: pallet_custom_signatures::Config<Index = u32>,
+    Extrinsic: Encode + ExtrinsicT<Call = <Runtime as frame_system::Config>::Call>,
+    <Runtime as frame_system::Config>::Call: From<pallet_custom_signatures::Call<Runtime>>,
+{
+    let call = sp_core::bytes::from_hex(&params.call)
+        .ok()
+        .and_then(|call| {
+            <Runtime as pallet_custom_signatures::Config>::Call::decode(&mut &call[..]).ok()
+        })
+        .ok_or("Invalid runtime call")?;
+    let pair = ecdsa::Pair::from_string(&params.suri, None).map_err(|_| "Invalid secret URI")?;
+
+    let payload = signer::payload(
+        Runtime::CallMagicNumber::get(),
+        params.nonce,
+        &call,
+        Some(mortality),
+    );
+    let call = pallet_custom_signatures::Call::<Runtime>::versioned_call {
+        call: Box::new(call),
+        signer: signer(&pair),
+        signature: signature(&pair, &payload),
+        version: PayloadVersion::V1(mortality.era),
+        nonce: params.nonce,
+    };
+    let extrinsic =
+        Extrinsic::new(call.into(), None).ok_or("Unsigned extrinsic isn't supported")?;
+    Ok(extrinsic.encode())
+}
+
 /// Parse command line arguments into service configuration.
 pub fn run() -> Result<()> {
     let cli = Cli::from_args();
@@ -487,6 +565,62 @@ pub fn run() -> Result<()> {
 
             Ok(())
         }
+        Some(Subcommand::SignEthCall(params)) => {
+            let chain_spec = load_spec(
+                &params.chain.clone().unwrap_or_default(),
+                params.parachain_id,
+            )?;
+            let genesis: Block = generate_genesis_block(&chain_spec)?;
+            let mortality = eth_call_mortality(params, genesis.header().hash())?;
+            let eth_sign = |pair: &ecdsa::Pair, payload: &[u8]| {
+                signer::eth_sign(pair, payload).to_vec()
+            };
+
+            let extrinsic = if chain_spec.is_astar() {
+                eth_call_extrinsic::<astar_runtime::Runtime, astar_runtime::UncheckedExtrinsic>(
+                    params,
+                    &mortality,
+                    signer::account,
+                    eth_sign,
+                )?
+            } else if chain_spec.is_shiden() {
+                eth_call_extrinsic::<shiden_runtime::Runtime, shiden_runtime::UncheckedExtrinsic>(
+                    params,
+                    &mortality,
+                    signer::account,
+                    eth_sign,
+                )?
+            } else if chain_spec.is_shibuya() {
+                eth_call_extrinsic::<shibuya_runtime::Runtime, shibuya_runtime::UncheckedExtrinsic>(
+                    params,
+                    &mortality,
+                    |pair| {
+                        shibuya_runtime::EvmAddressMapping::into_account_id(signer::address(pair))
+                    },
+                    |pair, payload| {
+                        AnySignature::<shibuya_runtime::EthCallDomain>::Ethereum(
+                            EthereumSignature(signer::eth_sign(pair, payload)),
+                        )
+                        .encode()
+                    },
+                )?
+            } else {
+                eth_call_extrinsic::<local_runtime::Runtime, local_runtime::UncheckedExtrinsic>(
+                    params,
+                    &mortality,
+                    |pair| local_runtime::EvmAddressMapping::into_account_id(signer::address(pair)),
+                    |pair, payload| {
+                        AnySignature::<local_runtime::EthCallDomain>::Ethereum(EthereumSignature(
+                            signer::eth_sign(pair, payload),
+                        ))
+                        .encode()
+                    },
+                )?
+            };
+
+            println!("0x{:?}", HexDisplay::from(&extrinsic));
+            Ok(())
+        }
         Some(Subcommand::Key(cmd)) => cmd.run(&cli),
         Some(Subcommand::Sign(cmd)) => cmd.run(),
         Some(Subcommand::Verify(cmd)) => cmd.run(),
@@ -2,8 +2,7 @@
 
 use local_runtime::{
     wasm_binary_unwrap, AccountId, AuraConfig, AuraId, BalancesConfig, EVMConfig, GenesisConfig,
-    GrandpaConfig, GrandpaId, LocalNetworkPrecompiles, Signature, SudoConfig, SystemConfig,
-    VestingConfig,
+    GrandpaConfig, GrandpaId, Signature, SudoConfig, SystemConfig, VestingConfig,
 };
 use sc_service::ChainType;
 use sp_core::{sr25519, Pair, Public};
@@ -71,11 +70,6 @@ fn testnet_genesis(
     root_key: AccountId,
     endowed_accounts: Vec<AccountId>,
 ) -> GenesisConfig {
-    // This is supposed the be the simplest bytecode to revert without returning any data.
-    // We will pre-deploy it under all of our precompiles to ensure they can be called from
-    // within contracts.
-    // (PUSH1 0x00 PUSH1 0x00 REVERT)
-    let revert_bytecode = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
     GenesisConfig {
         system: SystemConfig {
             code: wasm_binary_unwrap().to_vec(),
@@ -99,22 +93,10 @@ fn testnet_genesis(
                 .collect(),
         },
         evm: EVMConfig {
-            // We need _some_ code inserted at the precompile address so that
-            // the evm will actually call the address.
-            accounts: LocalNetworkPrecompiles::<()>::used_addresses()
-                .map(|addr| {
-                    (
-                        addr,
-                        pallet_evm::GenesisAccount {
-                            nonce: Default::default(),
-                            balance: Default::default(),
-                            storage: Default::default(),
-                            code: revert_bytecode.clone(),
-                        },
-                    )
-                })
-                .collect(),
+            accounts: Default::default(),
         },
+        // Dummy code is inserted under the precompile addresses so that EVM will call them.
+        precompile_registry: Default::default(),
         ethereum: Default::default(),
         sudo: SudoConfig { key: root_key },
     }
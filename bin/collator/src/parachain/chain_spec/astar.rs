@@ -1,8 +1,8 @@
 //! Astar chain specifications.
 
 use astar_runtime::{
-    wasm_binary_unwrap, AccountId, AstarNetworkPrecompiles, AuraId, Balance, EVMConfig,
-    ParachainInfoConfig, Signature, SystemConfig, ASTR,
+    wasm_binary_unwrap, AccountId, AuraId, Balance, EVMConfig, ParachainInfoConfig, Signature,
+    SystemConfig, ASTR,
 };
 use cumulus_primitives_core::ParaId;
 use sc_service::ChainType;
@@ -111,12 +111,6 @@ fn make_genesis(
         ),
     ];
 
-    // This is supposed the be the simplest bytecode to revert without returning any data.
-    // We will pre-deploy it under all of our precompiles to ensure they can be called from
-    // within contracts.
-    // (PUSH1 0x00 PUSH1 0x00 REVERT)
-    let revert_bytecode = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
-
     astar_runtime::GenesisConfig {
         system: SystemConfig {
             code: wasm_binary_unwrap().to_vec(),
@@ -142,22 +136,10 @@ fn make_genesis(
             invulnerables: authorities.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
         },
         evm: EVMConfig {
-            // We need _some_ code inserted at the precompile address so that
-            // the evm will actually call the address.
-            accounts: AstarNetworkPrecompiles::<()>::used_addresses()
-                .map(|addr| {
-                    (
-                        addr,
-                        pallet_evm::GenesisAccount {
-                            nonce: Default::default(),
-                            balance: Default::default(),
-                            storage: Default::default(),
-                            code: revert_bytecode.clone(),
-                        },
-                    )
-                })
-                .collect(),
+            accounts: Default::default(),
         },
+        // Dummy code is inserted under the precompile addresses so that EVM will call them.
+        precompile_registry: Default::default(),
         ethereum: Default::default(),
     }
 }
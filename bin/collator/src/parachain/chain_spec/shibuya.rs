@@ -7,8 +7,7 @@ use serde::{Deserialize, Serialize};
 use shibuya_runtime::{
     wasm_binary_unwrap, AccountId, AuraConfig, AuraId, Balance, BalancesConfig,
     CollatorSelectionConfig, EVMConfig, GenesisConfig, ParachainInfoConfig, SessionConfig,
-    SessionKeys, ShibuyaNetworkPrecompiles, Signature, SudoConfig, SystemConfig, VestingConfig,
-    SDN,
+    SessionKeys, Signature, SudoConfig, SystemConfig, VestingConfig, SDN,
 };
 use sp_core::{sr25519, Pair, Public};
 
@@ -70,12 +69,6 @@ fn make_genesis(
         ),
     ];
 
-    // This is supposed the be the simplest bytecode to revert without returning any data.
-    // We will pre-deploy it under all of our precompiles to ensure they can be called from
-    // within contracts.
-    // (PUSH1 0x00 PUSH1 0x00 REVERT)
-    let revert_bytecode = vec![0x60, 0x00, 0x60, 0x00, 0xFD];
-
     GenesisConfig {
         system: SystemConfig {
             code: wasm_binary_unwrap().to_vec(),
@@ -101,22 +94,10 @@ fn make_genesis(
             invulnerables: authorities.iter().map(|x| x.0.clone()).collect::<Vec<_>>(),
         },
         evm: EVMConfig {
-            // We need _some_ code inserted at the precompile address so that
-            // the evm will actually call the address.
-            accounts: ShibuyaNetworkPrecompiles::<()>::used_addresses()
-                .map(|addr| {
-                    (
-                        addr,
-                        pallet_evm::GenesisAccount {
-                            nonce: Default::default(),
-                            balance: Default::default(),
-                            storage: Default::default(),
-                            code: revert_bytecode.clone(),
-                        },
-                    )
-                })
-                .collect(),
+            accounts: Default::default(),
         },
+        // Dummy code is inserted under the precompile addresses so that EVM will call them.
+        precompile_registry: Default::default(),
         ethereum: Default::default(),
     }
 }
@@ -0,0 +1,149 @@
+//! # Address Registry Pallet
+//!
+//! - [`Config`]
+//!
+//! ## Overview
+//!
+//! EVM maps each Ethereum address to a hash-derived account, there is no way back from the
+//! account to the address. This pallet lets the account bind preferred Ethereum address,
+//! so contracts and off-chain tools could find the address of the account and vice versa.
+//!
+//! Address could be bound to a single account only. Control of the address is proven by
+//! Ethereum `personal_sign` signature of `bind_payload`.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `bind` - Bind Ethereum address to the account of origin, previous binding is replaced.
+//! - `unbind` - Remove Ethereum address binding of the account of origin.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use codec::Encode;
+    use frame_support::pallet_prelude::*;
+    use frame_system::{ensure_signed, pallet_prelude::*};
+    use pallet_custom_signatures::{ethereum::EthereumSignature, evm::RecoverAddress};
+    use sp_core::H160;
+    use sp_runtime::traits::Zero;
+    use sp_std::prelude::*;
+
+    /// Weight of Ethereum address recovery from the `bind` signature.
+    pub const SIGNATURE_RECOVERY_WEIGHT: Weight = 50_000_000;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    /// Ethereum address bound to the account.
+    #[pallet::storage]
+    #[pallet::getter(fn address_of)]
+    pub type Addresses<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, H160>;
+
+    /// Account bound to the Ethereum address.
+    #[pallet::storage]
+    #[pallet::getter(fn account_of)]
+    pub type Accounts<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Ethereum address bound to the account. \[account, address\]
+        AddressBound(T::AccountId, H160),
+        /// Ethereum address binding removed. \[account, address\]
+        AddressUnbound(T::AccountId, H160),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Signature isn't made by the address key.
+        BadSignature,
+        /// Address is already bound to another account.
+        AlreadyBound,
+        /// Account has no bound address.
+        NotBound,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Bind Ethereum `address` to the account of origin, previous binding is replaced.
+        ///
+        /// The dispatch origin must be Signed. `signature` is made by `personal_sign` of the
+        /// `bind_payload`.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - One signature recovery.
+        /// - Two DB reads and three writes (bindings).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(
+            T::DbWeight::get().reads_writes(2, 4).saturating_add(SIGNATURE_RECOVERY_WEIGHT)
+        )]
+        pub fn bind(
+            origin: OriginFor<T>,
+            address: H160,
+            signature: [u8; 65],
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let signers = EthereumSignature(signature).recover_addresses(&Self::bind_payload(&who));
+            ensure!(signers.contains(&address), Error::<T>::BadSignature);
+            ensure!(
+                Self::account_of(&address).map_or(true, |bound| bound == who),
+                Error::<T>::AlreadyBound
+            );
+
+            if let Some(previous) = Addresses::<T>::get(&who) {
+                Accounts::<T>::remove(previous);
+            }
+            Addresses::<T>::insert(&who, address);
+            Accounts::<T>::insert(address, &who);
+
+            Self::deposit_event(Event::<T>::AddressBound(who, address));
+            Ok(().into())
+        }
+
+        /// Remove Ethereum address binding of the account of origin.
+        ///
+        /// The dispatch origin must be Signed.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - One DB read and two writes (bindings).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 3))]
+        pub fn unbind(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            let address = Addresses::<T>::take(&who).ok_or(Error::<T>::NotBound)?;
+            Accounts::<T>::remove(address);
+
+            Self::deposit_event(Event::<T>::AddressUnbound(who, address));
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Encoded payload of address binding, it's `(b"bind", genesis_hash, account)`.
+        pub fn bind_payload(who: &T::AccountId) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            (b"bind", genesis_hash, who).encode()
+        }
+    }
+}
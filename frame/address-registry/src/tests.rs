@@ -0,0 +1,103 @@
+use super::{pallet::Error, Event, *};
+use frame_support::{assert_noop, assert_ok};
+use mock::*;
+use pallet_custom_signatures::signer;
+use sp_core::{ecdsa, Pair};
+use sp_runtime::traits::BadOrigin;
+
+fn bind_signature(pair: &ecdsa::Pair, who: AccountId) -> [u8; 65] {
+    signer::eth_sign(pair, &AddressRegistry::bind_payload(&who))
+}
+
+#[test]
+fn bind_and_unbind_are_ok() {
+    ExternalityBuilder::build().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let address = signer::address(&pair);
+
+        assert_ok!(AddressRegistry::bind(
+            Origin::signed(ALICE),
+            address,
+            bind_signature(&pair, ALICE),
+        ));
+        assert_eq!(AddressRegistry::address_of(ALICE), Some(address));
+        assert_eq!(AddressRegistry::account_of(address), Some(ALICE));
+
+        // Another address replaces the previous one
+        let other_pair = ecdsa::Pair::from_seed(&[2; 32]);
+        let other_address = signer::address(&other_pair);
+        assert_ok!(AddressRegistry::bind(
+            Origin::signed(ALICE),
+            other_address,
+            bind_signature(&other_pair, ALICE),
+        ));
+        assert_eq!(AddressRegistry::address_of(ALICE), Some(other_address));
+        assert_eq!(AddressRegistry::account_of(address), None);
+        assert_eq!(AddressRegistry::account_of(other_address), Some(ALICE));
+
+        assert_ok!(AddressRegistry::unbind(Origin::signed(ALICE)));
+        assert_eq!(AddressRegistry::address_of(ALICE), None);
+        assert_eq!(AddressRegistry::account_of(other_address), None);
+
+        assert_eq!(
+            address_registry_events(),
+            vec![
+                Event::AddressBound(ALICE, address),
+                Event::AddressBound(ALICE, other_address),
+                Event::AddressUnbound(ALICE, other_address),
+            ]
+        );
+    })
+}
+
+#[test]
+fn bind_is_checked() {
+    ExternalityBuilder::build().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&[1; 32]);
+        let address = signer::address(&pair);
+        let signature = bind_signature(&pair, ALICE);
+
+        assert_noop!(
+            AddressRegistry::bind(Origin::root(), address, signature),
+            BadOrigin
+        );
+        // Signature of another account payload
+        assert_noop!(
+            AddressRegistry::bind(Origin::signed(BOB), address, signature),
+            Error::<TestRuntime>::BadSignature
+        );
+        // Signature of another address
+        let other_address = signer::address(&ecdsa::Pair::from_seed(&[2; 32]));
+        assert_noop!(
+            AddressRegistry::bind(Origin::signed(ALICE), other_address, signature),
+            Error::<TestRuntime>::BadSignature
+        );
+
+        assert_ok!(AddressRegistry::bind(
+            Origin::signed(ALICE),
+            address,
+            signature
+        ));
+        // Rebinding to the same account is fine
+        assert_ok!(AddressRegistry::bind(
+            Origin::signed(ALICE),
+            address,
+            signature
+        ));
+        assert_noop!(
+            AddressRegistry::bind(Origin::signed(BOB), address, bind_signature(&pair, BOB)),
+            Error::<TestRuntime>::AlreadyBound
+        );
+    })
+}
+
+#[test]
+fn unbind_is_checked() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_noop!(AddressRegistry::unbind(Origin::root()), BadOrigin);
+        assert_noop!(
+            AddressRegistry::unbind(Origin::signed(ALICE)),
+            Error::<TestRuntime>::NotBound
+        );
+    })
+}
@@ -0,0 +1,16 @@
+//! Primitives shared by the block reward pallet and the pallets that feed it, so providers
+//! don't have to depend on the block reward pallet itself.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Source of the total value locked (TVL) in the network.
+pub trait TvlProvider<Balance> {
+    /// Total amount of currency locked at the moment.
+    fn tvl() -> Balance;
+}
+
+impl<Balance: Default> TvlProvider<Balance> for () {
+    fn tvl() -> Balance {
+        Default::default()
+    }
+}
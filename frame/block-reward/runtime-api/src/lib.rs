@@ -0,0 +1,17 @@
+//! Runtime API definition for the block reward pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait BlockRewardApi<BlockNumber, Balance> where
+        BlockNumber: Codec,
+        Balance: Codec,
+    {
+        /// Expected block reward for the given block number according to the active emission curve.
+        ///
+        /// Note: TVL adjustment isn't applied, the whole issuance of the block is returned.
+        fn block_reward(at: BlockNumber) -> Balance;
+    }
+}
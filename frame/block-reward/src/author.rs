@@ -0,0 +1,48 @@
+//! Block author reward handler.
+//!
+//! `AuthorReward` could be used as a part of `OnBlockReward` handler to pay the block
+//! producer directly instead of sending the whole collators reward to the staking pot:
+//! ```nocompile
+//! parameter_types! {
+//!     pub const AuthorRewardPercentage: Perbill = Perbill::from_percent(50);
+//! }
+//! type ToBlockAuthor = AuthorReward<Runtime, Balances, AuthorRewardPercentage, ToStakingPot>;
+//! ```
+//!
+//! The block author is noted by the block reward pallet as an `EventHandler` of
+//! `pallet_authorship`, e.g. `type EventHandler = (CollatorSelection, BlockReward);`.
+
+use frame_support::traits::{Currency, Get, Imbalance, OnUnbalanced};
+use sp_runtime::Perbill;
+use sp_std::marker::PhantomData;
+
+type NegativeImbalanceOf<T, C> =
+    <C as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+
+/// Pays `AuthorPercentage` of the reward to the author of current block, the remainder goes to `Pot`.
+///
+/// When block author isn't noted the whole reward goes to `Pot`.
+pub struct AuthorReward<T, C, AuthorPercentage, Pot>(PhantomData<(T, C, AuthorPercentage, Pot)>);
+
+impl<T, C, AuthorPercentage, Pot> OnUnbalanced<NegativeImbalanceOf<T, C>>
+    for AuthorReward<T, C, AuthorPercentage, Pot>
+where
+    T: crate::Config,
+    C: Currency<T::AccountId>,
+    AuthorPercentage: Get<Perbill>,
+    Pot: OnUnbalanced<NegativeImbalanceOf<T, C>>,
+{
+    fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T, C>) {
+        // Authorship notes the default account when the author isn't found
+        let author = match crate::Pallet::<T>::current_author() {
+            Some(author) if author != Default::default() => author,
+            _ => return Pot::on_unbalanced(amount),
+        };
+
+        let author_amount = AuthorPercentage::get() * amount.peek();
+        let (author_reward, pot_reward) = amount.split(author_amount);
+
+        C::resolve_creating(&author, author_reward);
+        Pot::on_unbalanced(pot_reward);
+    }
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::Pallet as BlockReward;
+
+use frame_benchmarking::{benchmarks, impl_benchmark_test_suite};
+use frame_support::traits::OnFinalize;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Zero;
+
+/// Sorted schedule of `items` items.
+fn schedule<T: Config>(items: u32) -> Vec<(T::BlockNumber, BalanceOf<T>)> {
+    (0..items).map(|i| (i.into(), 1_000u32.into())).collect()
+}
+
+benchmarks! {
+
+    on_finalize {
+        // The heaviest curve: schedule is decoded and iterated up to the last item
+        let s in 1 .. T::MaxScheduleItems::get();
+        ActiveEmissionCurve::<T>::put(EmissionCurve::Schedule(schedule::<T>(s)));
+        let block_number: T::BlockNumber = s.into();
+    }: {
+        BlockReward::<T>::on_finalize(block_number);
+    }
+    verify {
+        assert!(!BlockReward::<T>::total_issued_reward().is_zero());
+    }
+
+    set_emission_curve {
+        let s in 0 .. T::MaxScheduleItems::get();
+        let curve = EmissionCurve::Schedule(schedule::<T>(s));
+    }: _(RawOrigin::Root, curve.clone())
+    verify {
+        assert_eq!(BlockReward::<T>::emission_curve(), Some(curve));
+    }
+}
+
+impl_benchmark_test_suite!(
+    BlockReward,
+    crate::mock::ExternalityBuilder::build(),
+    crate::mock::TestRuntime,
+);
@@ -8,12 +8,34 @@
 //!
 //! ## Interface
 //!
-//! This pallet implements the `OnTimestampSet` trait to handle block production.
-//! Note: We assume that it's impossible to set timestamp two times in a block.
+//! Block reward is issued in `on_finalize` hook, its weight is reserved in `on_initialize`.
+//! Each issuance emits `BlockRewardIssued` event and is accumulated in `TotalIssuedReward`.
+//!
+//! ### Dispatchable Functions
+//!
+//! - `set_emission_curve` - Set block reward emission curve, root origin is required.
+//!
+//! ## Emission curve
+//!
+//! Amount of issuance for each block is defined by `EmissionCurve` stored on chain:
+//! - `Constant` - the same reward for each block;
+//! - `Schedule` - fixed rewards for block number ranges;
+//! - `ExponentialDecay` - reward decays by given ratio every period;
+//! - `TargetInflation` - reward is derived from target annual inflation of total issuance.
+//!
+//! `RewardAmount` is used for each block when no curve is set.
+//!
+//! ## TVL adjusted reward
+//!
+//! `TvlRewardPortion` of each block reward is sensitive to the total value locked (TVL)
+//! reported by `TvlProvider`. When TVL reaches `IdealTvlPercentage` of the total issuance
+//! the whole portion goes to `OnTvlAdjustedReward`, below that threshold it is scaled down
+//! linearly and the unused part goes to `OnUnusedTvlReward`. Set `OnUnusedTvlReward` to `()`
+//! to burn the unused part, i.e. don't mint it at all.
 //!
 //! ## Usage
 //!
-//! 1. Pallet should be set as a handler of `OnTimestampSet`.
+//! 1. Pallet hooks should be included into runtime, i.e. `Pallet` part of `construct_runtime`.
 //! 2. `OnBlockReward` handler should be defined as an impl of `OnUnbalanced` trait. For example:
 //! ```nocompile
 //! type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
@@ -25,42 +47,315 @@
 //! }
 //! ```
 //! 3. Set `RewardAmount` to desiced block reward value in native currency.
+//! 4. Set `TvlProvider` (e.g. dapps staking pallet) and `TvlRewardPortion` when TVL adjusted
+//! reward should be used, zero portion disables it.
+//! 5. Optionally use `author::AuthorReward` in `OnBlockReward` to pay the block author directly,
+//! the pallet should be added to `EventHandler` of `pallet_authorship` then.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{Perbill, RuntimeDebug};
+use sp_std::prelude::*;
+
 pub use pallet::*;
 
+/// Block author reward handler.
+pub mod author;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+pub use pallet_block_reward_primitives::TvlProvider;
+
+/// Block reward emission curve.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum EmissionCurve<BlockNumber, Balance> {
+    /// The same reward for each block.
+    Constant(Balance),
+    /// Fixed rewards by block number ranges.
+    ///
+    /// Each item is `(first_block, reward)`, the reward is active until the next item
+    /// first block. Items must be sorted by first block, no reward before the first item.
+    /// Number of items is limited by `MaxScheduleItems`.
+    Schedule(Vec<(BlockNumber, Balance)>),
+    /// Reward decreases by `decay` of the current value every `period` blocks.
+    ExponentialDecay {
+        /// Block number when `initial` reward is issued, no reward before it.
+        start: BlockNumber,
+        /// Reward for the first period.
+        initial: Balance,
+        /// Number of blocks in period.
+        period: BlockNumber,
+        /// Part of reward cut at the end of each period.
+        decay: Perbill,
+    },
+    /// Reward keeps annual inflation of total issuance at the target level.
+    TargetInflation {
+        /// Target annual inflation.
+        annual_inflation: Perbill,
+        /// Expected number of blocks per year.
+        blocks_per_year: BlockNumber,
+    },
+}
+
+impl<BlockNumber, Balance> EmissionCurve<BlockNumber, Balance> {
+    /// Number of `Schedule` items, zero for other curves.
+    pub fn schedule_len(&self) -> u32 {
+        match self {
+            EmissionCurve::Schedule(items) => items.len() as u32,
+            _ => 0,
+        }
+    }
+}
+
 #[frame_support::pallet]
 pub mod pallet {
+    use super::{EmissionCurve, TvlProvider, WeightInfo};
     use frame_support::pallet_prelude::*;
-    use frame_support::traits::{Currency, OnTimestampSet, OnUnbalanced};
+    use frame_support::traits::{Currency, Imbalance, OnUnbalanced};
+    use frame_system::{ensure_root, pallet_prelude::*};
+    use sp_runtime::{
+        traits::{Saturating, UniqueSaturatedInto, Zero},
+        PerThing, Perbill,
+    };
 
     /// The balance type of this pallet.
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    // Negative imbalance type of this pallet.
+    type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    /// Emission curve type of this pallet.
+    pub type EmissionCurveOf<T> = EmissionCurve<BlockNumberFor<T>, BalanceOf<T>>;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
         /// The currency trait.
         type Currency: Currency<Self::AccountId>;
 
         /// Handle block reward as imbalance.
-        type OnBlockReward: OnUnbalanced<
-            <Self::Currency as Currency<Self::AccountId>>::NegativeImbalance,
-        >;
+        type OnBlockReward: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
-        /// The amount of issuance for each block.
+        /// The amount of issuance for each block when emission curve isn't set.
         #[pallet::constant]
         type RewardAmount: Get<BalanceOf<Self>>;
+
+        /// Total value locked source used to adjust reward.
+        type TvlProvider: TvlProvider<BalanceOf<Self>>;
+
+        /// Percentage of total issuance that should be locked to get full TVL adjusted reward.
+        #[pallet::constant]
+        type IdealTvlPercentage: Get<Perbill>;
+
+        /// Portion of block reward that depends on TVL.
+        #[pallet::constant]
+        type TvlRewardPortion: Get<Perbill>;
+
+        /// Handle TVL adjusted part of block reward as imbalance.
+        type OnTvlAdjustedReward: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Handle part of block reward unused because of low TVL.
+        type OnUnusedTvlReward: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+        /// Maximum number of items in `Schedule` emission curve.
+        #[pallet::constant]
+        type MaxScheduleItems: Get<u32>;
+
+        /// Weight information for extrinsics and hooks in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
     pub struct Pallet<T>(PhantomData<T>);
 
-    impl<Moment, T: Config> OnTimestampSet<Moment> for Pallet<T> {
-        fn on_timestamp_set(_moment: Moment) {
-            let inflation = T::Currency::issue(T::RewardAmount::get());
-            T::OnBlockReward::on_unbalanced(inflation);
+    /// Active block reward emission curve.
+    #[pallet::storage]
+    #[pallet::getter(fn emission_curve)]
+    pub type ActiveEmissionCurve<T: Config> = StorageValue<_, EmissionCurveOf<T>>;
+
+    /// Total amount of block rewards issued by the pallet.
+    ///
+    /// Unused part of TVL adjusted reward is included only when it isn't burnt.
+    #[pallet::storage]
+    #[pallet::getter(fn total_issued_reward)]
+    pub type TotalIssuedReward<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// Author of the current block noted by `pallet_authorship`, removed on finalize.
+    #[pallet::storage]
+    #[pallet::getter(fn current_author)]
+    pub type CurrentAuthor<T: Config> = StorageValue<_, T::AccountId>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Block reward issued. \[amount\]
+        BlockRewardIssued(BalanceOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Schedule items aren't sorted by first block.
+        UnsortedSchedule,
+        /// Period or number of blocks per year is zero.
+        ZeroPeriod,
+        /// Schedule has more than `MaxScheduleItems` items.
+        TooManyScheduleItems,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Set block reward emission curve, it's applied since the next block.
+        ///
+        /// The dispatch origin must be Root.
+        ///
+        /// # <weight>
+        /// - Weight: O(S) where S is the number of schedule items, bounded by `MaxScheduleItems`
+        /// - Write ActiveEmissionCurve
+        /// # </weight>
+        #[pallet::weight(T::WeightInfo::set_emission_curve(curve.schedule_len()))]
+        pub fn set_emission_curve(
+            origin: OriginFor<T>,
+            curve: EmissionCurveOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            match &curve {
+                EmissionCurve::Schedule(items) => {
+                    ensure!(
+                        items.len() as u32 <= T::MaxScheduleItems::get(),
+                        Error::<T>::TooManyScheduleItems,
+                    );
+                    ensure!(
+                        items.windows(2).all(|w| w[0].0 < w[1].0),
+                        Error::<T>::UnsortedSchedule,
+                    )
+                }
+                EmissionCurve::ExponentialDecay { period, .. } => {
+                    ensure!(!period.is_zero(), Error::<T>::ZeroPeriod)
+                }
+                EmissionCurve::TargetInflation {
+                    blocks_per_year, ..
+                } => ensure!(!blocks_per_year.is_zero(), Error::<T>::ZeroPeriod),
+                EmissionCurve::Constant(_) => (),
+            }
+            ActiveEmissionCurve::<T>::put(curve);
+
+            Ok(().into())
+        }
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+            // Reward is issued on finalize, the weight should be reserved here.
+            // Curve isn't read yet, so the weight of the longest schedule is reserved.
+            T::WeightInfo::on_finalize(T::MaxScheduleItems::get())
+        }
+
+        fn on_finalize(now: BlockNumberFor<T>) {
+            let issuance = T::Currency::total_issuance();
+            Self::distribute(T::Currency::issue(Self::reward_at(now)));
+
+            // Unused part of TVL adjusted reward could be burnt, account the actual issuance
+            let amount = T::Currency::total_issuance().saturating_sub(issuance);
+            TotalIssuedReward::<T>::mutate(|total| *total = total.saturating_add(amount));
+            CurrentAuthor::<T>::kill();
+
+            Self::deposit_event(Event::<T>::BlockRewardIssued(amount));
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Block reward issued at block `now` according to the active emission curve.
+        ///
+        /// Note: `TargetInflation` curve uses current total issuance.
+        pub fn reward_at(now: BlockNumberFor<T>) -> BalanceOf<T> {
+            match Self::emission_curve() {
+                None => T::RewardAmount::get(),
+                Some(EmissionCurve::Constant(reward)) => reward,
+                Some(EmissionCurve::Schedule(items)) => items
+                    .into_iter()
+                    .take_while(|(first_block, _)| *first_block <= now)
+                    .last()
+                    .map(|(_, reward)| reward)
+                    .unwrap_or_else(Zero::zero),
+                Some(EmissionCurve::ExponentialDecay {
+                    start,
+                    initial,
+                    period,
+                    decay,
+                }) => {
+                    if now < start {
+                        return Zero::zero();
+                    }
+                    let periods: u32 = ((now - start) / period).unique_saturated_into();
+                    decay.left_from_one().saturating_pow(periods as usize) * initial
+                }
+                Some(EmissionCurve::TargetInflation {
+                    annual_inflation,
+                    blocks_per_year,
+                }) => {
+                    let blocks_per_year: u32 = blocks_per_year.unique_saturated_into();
+                    (annual_inflation * T::Currency::total_issuance())
+                        / BalanceOf::<T>::from(blocks_per_year)
+                }
+            }
+        }
+
+        /// Split block reward into fixed and TVL adjusted parts and pass them to handlers.
+        fn distribute(inflation: NegativeImbalanceOf<T>) {
+            let tvl_portion = T::TvlRewardPortion::get() * inflation.peek();
+            let (tvl_reward, fixed_reward) = inflation.split(tvl_portion);
+
+            let adjusted_amount = Self::tvl_adjusted_reward(tvl_reward.peek());
+            let (adjusted_reward, unused_reward) = tvl_reward.split(adjusted_amount);
+
+            T::OnBlockReward::on_unbalanced(fixed_reward);
+            T::OnTvlAdjustedReward::on_unbalanced(adjusted_reward);
+            T::OnUnusedTvlReward::on_unbalanced(unused_reward);
         }
+
+        /// Scale `amount` by ratio of current TVL to the ideal one.
+        ///
+        /// Full `amount` is returned when TVL is equal or above the ideal.
+        pub fn tvl_adjusted_reward(amount: BalanceOf<T>) -> BalanceOf<T> {
+            let ideal_tvl = T::IdealTvlPercentage::get() * T::Currency::total_issuance();
+            let tvl = T::TvlProvider::tvl();
+
+            if tvl >= ideal_tvl {
+                amount
+            } else {
+                Perbill::from_rational(tvl, ideal_tvl) * amount
+            }
+        }
+    }
+
+    /// Notes the block author for `author::AuthorReward`.
+    ///
+    /// The author is noted in `on_initialize` of `pallet_authorship`, so it's known during
+    /// the whole block regardless of the hooks order.
+    impl<T: Config> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>>
+        for Pallet<T>
+    {
+        fn note_author(author: T::AccountId) {
+            CurrentAuthor::<T>::put(author);
+        }
+
+        fn note_uncle(_author: T::AccountId, _age: BlockNumberFor<T>) {}
     }
 }
@@ -0,0 +1,226 @@
+use crate::{self as pallet_block_reward, author::AuthorReward, TvlProvider};
+
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{Currency, FindAuthor, Imbalance, OnFinalize, OnInitialize, OnUnbalanced},
+    ConsensusEngineId,
+};
+use sp_core::H256;
+
+use sp_io::TestExternalities;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
+
+pub(crate) const BLOCK_REWARD: Balance = 1_000_000;
+/// Total issuance before the first block, block reward added to it gives a round number.
+pub(crate) const INITIAL_ISSUANCE: Balance = 9_000_000;
+
+pub(crate) const DAPPS_STAKING_POT: AccountId = 100;
+pub(crate) const TREASURY_POT: AccountId = 101;
+pub(crate) const COLLATOR_POT: AccountId = 102;
+pub(crate) const TVL_ADJUSTED_POT: AccountId = 103;
+pub(crate) const BLOCK_AUTHOR: AccountId = 200;
+pub(crate) const ANOTHER_AUTHOR: AccountId = 201;
+
+construct_runtime!(
+    pub enum TestRuntime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        // The same order as in runtimes: `Authorship` is finalized before `BlockReward`
+        BlockReward: pallet_block_reward::{Pallet, Call, Storage, Event<T>},
+        Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Origin = Origin;
+    type Index = u64;
+    type Call = Call;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const MaxLocks: u32 = 4;
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const UncleGenerations: BlockNumber = 5;
+    pub static BlockAuthor: Option<AccountId> = None;
+}
+
+pub struct MockFindAuthor;
+impl FindAuthor<AccountId> for MockFindAuthor {
+    fn find_author<'a, I>(_digests: I) -> Option<AccountId>
+    where
+        I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+    {
+        BlockAuthor::get()
+    }
+}
+
+impl pallet_authorship::Config for TestRuntime {
+    type FindAuthor = MockFindAuthor;
+    type UncleGenerations = UncleGenerations;
+    type FilterUncle = ();
+    type EventHandler = BlockReward;
+}
+
+pub struct ToCollatorPot;
+impl OnUnbalanced<NegativeImbalance> for ToCollatorPot {
+    fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+        Balances::resolve_creating(&COLLATOR_POT, amount);
+    }
+}
+
+parameter_types! {
+    pub static AuthorRewardPercentage: Perbill = Perbill::from_percent(0);
+}
+
+type ToBlockAuthor = AuthorReward<TestRuntime, Balances, AuthorRewardPercentage, ToCollatorPot>;
+
+/// Block reward split as it's done in Shiden runtime.
+pub struct OnBlockReward;
+impl OnUnbalanced<NegativeImbalance> for OnBlockReward {
+    fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+        let (dapps, maintain) = amount.ration(50, 50);
+        // dapp staking block reward
+        Balances::resolve_creating(&DAPPS_STAKING_POT, dapps);
+
+        let (treasury, collators) = maintain.ration(40, 10);
+        // treasury slice of block reward
+        Balances::resolve_creating(&TREASURY_POT, treasury);
+        // collators block reward
+        ToBlockAuthor::on_unbalanced(collators);
+    }
+}
+
+pub struct OnTvlAdjustedReward;
+impl OnUnbalanced<NegativeImbalance> for OnTvlAdjustedReward {
+    fn on_nonzero_unbalanced(amount: NegativeImbalance) {
+        Balances::resolve_creating(&TVL_ADJUSTED_POT, amount);
+    }
+}
+
+parameter_types! {
+    pub const RewardAmount: Balance = BLOCK_REWARD;
+    pub const IdealTvlPercentage: Perbill = Perbill::from_percent(50);
+    pub static TvlRewardPortion: Perbill = Perbill::from_percent(0);
+    pub const MaxScheduleItems: u32 = 32;
+    pub static Tvl: Balance = 0;
+}
+
+pub struct MockTvl;
+impl TvlProvider<Balance> for MockTvl {
+    fn tvl() -> Balance {
+        Tvl::get()
+    }
+}
+
+impl pallet_block_reward::Config for TestRuntime {
+    type Event = Event;
+    type Currency = Balances;
+    type OnBlockReward = OnBlockReward;
+    type RewardAmount = RewardAmount;
+    type TvlProvider = MockTvl;
+    type IdealTvlPercentage = IdealTvlPercentage;
+    type TvlRewardPortion = TvlRewardPortion;
+    type OnTvlAdjustedReward = OnTvlAdjustedReward;
+    type OnUnusedTvlReward = ();
+    type MaxScheduleItems = MaxScheduleItems;
+    type WeightInfo = ();
+}
+
+pub struct ExternalityBuilder;
+
+impl ExternalityBuilder {
+    pub fn build() -> TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<TestRuntime>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<TestRuntime> {
+            balances: vec![(1, INITIAL_ISSUANCE)],
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
+
+/// Used to run to the specified block number, hooks are called in the runtime order
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        AllPallets::on_finalize(System::block_number());
+        System::set_block_number(System::block_number() + 1);
+        AllPallets::on_initialize(System::block_number());
+    }
+}
+
+/// Used to get a vec of all block reward events
+pub fn block_reward_events() -> Vec<crate::Event<TestRuntime>> {
+    System::events()
+        .into_iter()
+        .map(|r| r.event)
+        .filter_map(|e| {
+            if let Event::BlockReward(inner) = e {
+                Some(inner)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,232 @@
+use super::{pallet::Error, Event, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, OnInitialize},
+};
+use mock::*;
+use sp_runtime::{
+    traits::{BadOrigin, Zero},
+    Perbill,
+};
+
+#[test]
+fn reward_is_issued_on_finalize() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert!(BlockReward::total_issued_reward().is_zero());
+
+        run_to_block(2);
+
+        // Reward is split by the Shiden runtime proportions
+        assert_eq!(Balances::free_balance(&DAPPS_STAKING_POT), 500_000);
+        assert_eq!(Balances::free_balance(&TREASURY_POT), 400_000);
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 100_000);
+        assert!(Balances::free_balance(&TVL_ADJUSTED_POT).is_zero());
+
+        assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + BLOCK_REWARD);
+        assert_eq!(BlockReward::total_issued_reward(), BLOCK_REWARD);
+        assert_eq!(
+            block_reward_events(),
+            vec![Event::BlockRewardIssued(BLOCK_REWARD)]
+        );
+
+        run_to_block(4);
+
+        assert_eq!(Balances::free_balance(&DAPPS_STAKING_POT), 1_500_000);
+        assert_eq!(Balances::free_balance(&TREASURY_POT), 1_200_000);
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 300_000);
+        assert_eq!(BlockReward::total_issued_reward(), 3 * BLOCK_REWARD);
+        assert_eq!(block_reward_events().len(), 3);
+    })
+}
+
+#[test]
+fn on_initialize_reserves_reward_weight() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_eq!(
+            BlockReward::on_initialize(1),
+            <() as WeightInfo>::on_finalize(MaxScheduleItems::get())
+        );
+    })
+}
+
+#[test]
+fn tvl_adjusted_reward_is_ok() {
+    ExternalityBuilder::build().execute_with(|| {
+        TvlRewardPortion::set(&Perbill::from_percent(20));
+
+        // Total issuance is 10_000_000 with the block reward, TVL is 50% of ideal
+        Tvl::set(&2_500_000);
+        run_to_block(2);
+
+        assert_eq!(Balances::free_balance(&TVL_ADJUSTED_POT), 100_000);
+        assert_eq!(Balances::free_balance(&DAPPS_STAKING_POT), 400_000);
+        assert_eq!(Balances::free_balance(&TREASURY_POT), 320_000);
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 80_000);
+        // Unused part isn't minted
+        assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 900_000);
+        assert_eq!(BlockReward::total_issued_reward(), 900_000);
+        assert_eq!(
+            block_reward_events(),
+            vec![Event::BlockRewardIssued(900_000)]
+        );
+
+        // Full TVL adjusted reward above the ideal TVL
+        Tvl::set(&6_000_000);
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(&TVL_ADJUSTED_POT), 300_000);
+
+        // Nothing without TVL
+        Tvl::set(&0);
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(&TVL_ADJUSTED_POT), 300_000);
+        assert_eq!(Balances::free_balance(&DAPPS_STAKING_POT), 1_200_000);
+    })
+}
+
+#[test]
+fn author_reward_is_ok() {
+    ExternalityBuilder::build().execute_with(|| {
+        AuthorRewardPercentage::set(&Perbill::from_percent(50));
+
+        // Author is noted on initialize, the first block isn't initialized in tests
+        BlockAuthor::set(&Some(BLOCK_AUTHOR));
+        run_to_block(2);
+        assert!(Balances::free_balance(&BLOCK_AUTHOR).is_zero());
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 100_000);
+
+        // Half of collators reward goes to the block author
+        run_to_block(3);
+        assert_eq!(Balances::free_balance(&BLOCK_AUTHOR), 50_000);
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 150_000);
+
+        // Unknown author, everything goes to the pot
+        BlockAuthor::set(&None);
+        run_to_block(4);
+        assert_eq!(Balances::free_balance(&BLOCK_AUTHOR), 50_000);
+        assert_eq!(Balances::free_balance(&COLLATOR_POT), 250_000);
+
+        // Other destinations aren't affected
+        assert_eq!(Balances::free_balance(&DAPPS_STAKING_POT), 1_500_000);
+        assert_eq!(Balances::free_balance(&TREASURY_POT), 1_200_000);
+    })
+}
+
+#[test]
+fn author_is_not_kept_between_blocks() {
+    ExternalityBuilder::build().execute_with(|| {
+        AuthorRewardPercentage::set(&Perbill::from_percent(50));
+
+        // Hooks run in the runtime order, i.e. `Authorship` is finalized first
+        BlockAuthor::set(&Some(BLOCK_AUTHOR));
+        run_to_block(3);
+        BlockAuthor::set(&Some(ANOTHER_AUTHOR));
+        run_to_block(4);
+
+        // Each author is rewarded only for its own block
+        assert_eq!(Balances::free_balance(&BLOCK_AUTHOR), 50_000);
+        assert_eq!(Balances::free_balance(&ANOTHER_AUTHOR), 50_000);
+        assert!(BlockReward::current_author().is_none());
+    })
+}
+
+#[test]
+fn emission_curves_are_ok() {
+    ExternalityBuilder::build().execute_with(|| {
+        // Without curve constant reward is used
+        assert_eq!(BlockReward::reward_at(1), BLOCK_REWARD);
+        assert_eq!(BlockReward::reward_at(1_000_000), BLOCK_REWARD);
+
+        assert_ok!(BlockReward::set_emission_curve(
+            Origin::root(),
+            EmissionCurve::Constant(5)
+        ));
+        assert_eq!(BlockReward::reward_at(1), 5);
+
+        assert_ok!(BlockReward::set_emission_curve(
+            Origin::root(),
+            EmissionCurve::Schedule(vec![(10, 100), (20, 50)])
+        ));
+        assert!(BlockReward::reward_at(5).is_zero());
+        assert_eq!(BlockReward::reward_at(10), 100);
+        assert_eq!(BlockReward::reward_at(19), 100);
+        assert_eq!(BlockReward::reward_at(20), 50);
+        assert_eq!(BlockReward::reward_at(1_000), 50);
+
+        assert_ok!(BlockReward::set_emission_curve(
+            Origin::root(),
+            EmissionCurve::ExponentialDecay {
+                start: 10,
+                initial: 1_000,
+                period: 10,
+                decay: Perbill::from_percent(10),
+            }
+        ));
+        // No reward before the start
+        assert_eq!(BlockReward::reward_at(5), 0);
+        assert_eq!(BlockReward::reward_at(9), 0);
+        assert_eq!(BlockReward::reward_at(10), 1_000);
+        assert_eq!(BlockReward::reward_at(19), 1_000);
+        assert_eq!(BlockReward::reward_at(20), 900);
+        assert_eq!(BlockReward::reward_at(30), 810);
+        assert_eq!(BlockReward::reward_at(45), 729);
+
+        assert_ok!(BlockReward::set_emission_curve(
+            Origin::root(),
+            EmissionCurve::TargetInflation {
+                annual_inflation: Perbill::from_percent(10),
+                blocks_per_year: 100,
+            }
+        ));
+        assert_eq!(BlockReward::reward_at(1), 9_000);
+
+        // Active curve is used for issuance
+        run_to_block(2);
+        assert_eq!(BlockReward::total_issued_reward(), 9_000);
+        assert_eq!(Balances::total_issuance(), INITIAL_ISSUANCE + 9_000);
+    })
+}
+
+#[test]
+fn set_emission_curve_is_checked() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_noop!(
+            BlockReward::set_emission_curve(Origin::signed(1), EmissionCurve::Constant(5)),
+            BadOrigin,
+        );
+        assert_noop!(
+            BlockReward::set_emission_curve(
+                Origin::root(),
+                EmissionCurve::Schedule(vec![(20, 100), (10, 50)])
+            ),
+            Error::<TestRuntime>::UnsortedSchedule,
+        );
+        let schedule = (0..=MaxScheduleItems::get() as u64).map(|n| (n, 100)).collect();
+        assert_noop!(
+            BlockReward::set_emission_curve(Origin::root(), EmissionCurve::Schedule(schedule)),
+            Error::<TestRuntime>::TooManyScheduleItems,
+        );
+        assert_noop!(
+            BlockReward::set_emission_curve(
+                Origin::root(),
+                EmissionCurve::ExponentialDecay {
+                    start: 0,
+                    initial: 1_000,
+                    period: 0,
+                    decay: Perbill::from_percent(10),
+                }
+            ),
+            Error::<TestRuntime>::ZeroPeriod,
+        );
+        assert_noop!(
+            BlockReward::set_emission_curve(
+                Origin::root(),
+                EmissionCurve::TargetInflation {
+                    annual_inflation: Perbill::from_percent(10),
+                    blocks_per_year: 0,
+                }
+            ),
+            Error::<TestRuntime>::ZeroPeriod,
+        );
+        assert!(BlockReward::emission_curve().is_none());
+    })
+}
@@ -0,0 +1,34 @@
+//! Weights for `pallet_block_reward`
+//!
+//! There are no benchmark results yet, runtimes use `()` weights. `SubstrateWeight` should be
+//! generated by `benchmark --pallet pallet_block_reward` on the reference hardware.
+
+#![allow(unused_parens)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_block_reward.
+pub trait WeightInfo {
+    /// s - number of items in schedule emission curve
+    fn on_finalize(s: u32) -> Weight;
+    /// s - number of items in schedule emission curve
+    fn set_emission_curve(s: u32) -> Weight;
+}
+
+// Rough upper bounds used until the pallet is benchmarked
+impl WeightInfo for () {
+    fn on_finalize(s: u32) -> Weight {
+        (90_000_000 as Weight)
+            .saturating_add((200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn set_emission_curve(s: u32) -> Weight {
+        (5_000_000 as Weight)
+            .saturating_add((100_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+}
@@ -0,0 +1,192 @@
+//! # Collator Exit Pallet
+//!
+//! - [`Config`]
+//!
+//! ## Overview
+//!
+//! Managed exit of collation candidates registered in `pallet_collator_selection`.
+//!
+//! Candidate leaving directly via `pallet_collator_selection` gets its bond back immediately,
+//! while it still could be in the validator set of the current and the queued sessions.
+//! This pallet removes the candidate from the collator selection at once but keeps its bond
+//! reserved for `Cooldown` sessions.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `leave_intent` - Leave candidacy, the bond is released after `Cooldown` sessions.
+//! - `withdraw_bond` - Release the bond when cooldown is elapsed.
+//!
+//! ## Usage
+//!
+//! 1. Include pallet into runtime with `Pallet`, `Call`, `Storage` and `Event` parts.
+//! 2. Set `Cooldown` to the number of sessions the leaving collator may stay in the
+//! validator set, i.e. `2` for the current and the queued session.
+//! 3. Filter out `pallet_collator_selection::Call::leave_intent` in `BaseCallFilter`,
+//! otherwise candidates are able to skip the cooldown.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Bond of collator that has left candidacy.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Unbonding<Balance, SessionIndex> {
+    /// Reserved amount of candidacy bond.
+    pub amount: Balance,
+    /// First session when the bond could be released.
+    pub release_session: SessionIndex,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::Unbonding;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Currency, ReservableCurrency},
+        transactional,
+    };
+    use frame_system::{ensure_signed, pallet_prelude::*, RawOrigin};
+    use pallet_collator_selection::WeightInfo as _;
+    use sp_runtime::traits::Saturating;
+    use sp_staking::SessionIndex;
+
+    /// The balance type of this pallet.
+    pub type BalanceOf<T> = <<T as pallet_collator_selection::Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::Balance;
+
+    #[pallet::config]
+    pub trait Config:
+        frame_system::Config + pallet_session::Config + pallet_collator_selection::Config
+    {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Number of sessions the bond stays reserved after leaving candidacy.
+        #[pallet::constant]
+        type Cooldown: Get<SessionIndex>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    /// Bonds of collators that have left candidacy.
+    #[pallet::storage]
+    #[pallet::getter(fn leaving)]
+    pub type Leaving<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Unbonding<BalanceOf<T>, SessionIndex>,
+        OptionQuery,
+    >;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Collator left candidacy. \[collator, bond, release_session\]
+        LeaveIntent(T::AccountId, BalanceOf<T>, SessionIndex),
+        /// Candidacy bond released. \[collator, bond\]
+        BondReleased(T::AccountId, BalanceOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// Account isn't a collation candidate.
+        NotCandidate,
+        /// Account has already left candidacy, bond should be withdrawn first.
+        AlreadyLeaving,
+        /// Account hasn't left candidacy.
+        NotLeaving,
+        /// Bond can't be released before the release session.
+        CooldownNotElapsed,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Leave collation candidacy, the bond stays reserved for `Cooldown` sessions.
+        ///
+        /// The dispatch origin must be Signed by the candidate.
+        ///
+        /// # <weight>
+        /// - Weight of `pallet_collator_selection::leave_intent` with max candidates
+        /// - Read / Write Leaving, Session CurrentIndex
+        /// # </weight>
+        #[pallet::weight(
+            <T as pallet_collator_selection::Config>::WeightInfo::leave_intent(
+                T::MaxCandidates::get()
+            ).saturating_add(T::DbWeight::get().reads_writes(2, 1))
+        )]
+        #[transactional]
+        pub fn leave_intent(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                !Leaving::<T>::contains_key(&who),
+                Error::<T>::AlreadyLeaving
+            );
+
+            let amount = pallet_collator_selection::Pallet::<T>::candidates()
+                .into_iter()
+                .find(|candidate| candidate.who == who)
+                .map(|candidate| candidate.deposit)
+                .ok_or(Error::<T>::NotCandidate)?;
+
+            // Collator selection releases the bond, take it back for the cooldown period.
+            let post_info = pallet_collator_selection::Pallet::<T>::leave_intent(
+                RawOrigin::Signed(who.clone()).into(),
+            )?;
+            T::Currency::reserve(&who, amount)?;
+
+            let release_session =
+                pallet_session::Pallet::<T>::current_index().saturating_add(T::Cooldown::get());
+            Leaving::<T>::insert(
+                &who,
+                Unbonding {
+                    amount,
+                    release_session,
+                },
+            );
+
+            Self::deposit_event(Event::<T>::LeaveIntent(who, amount, release_session));
+            Ok(post_info
+                .actual_weight
+                .map(|weight| weight.saturating_add(T::DbWeight::get().reads_writes(2, 1)))
+                .into())
+        }
+
+        /// Release candidacy bond of the collator that has left candidacy.
+        ///
+        /// The dispatch origin must be Signed by the collator.
+        ///
+        /// # <weight>
+        /// - Weight: O(1)
+        /// - Read / Write Leaving, Session CurrentIndex
+        /// # </weight>
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+        pub fn withdraw_bond(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let unbonding = Leaving::<T>::get(&who).ok_or(Error::<T>::NotLeaving)?;
+            ensure!(
+                pallet_session::Pallet::<T>::current_index() >= unbonding.release_session,
+                Error::<T>::CooldownNotElapsed
+            );
+
+            T::Currency::unreserve(&who, unbonding.amount);
+            Leaving::<T>::remove(&who);
+
+            Self::deposit_event(Event::<T>::BondReleased(who, unbonding.amount));
+            Ok(().into())
+        }
+    }
+}
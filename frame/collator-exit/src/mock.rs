@@ -0,0 +1,209 @@
+use crate::{self as pallet_collator_exit};
+
+use frame_support::{
+    construct_runtime, parameter_types,
+    traits::{OnFinalize, OnInitialize},
+    PalletId,
+};
+use sp_core::H256;
+
+use sp_io::TestExternalities;
+use sp_runtime::{
+    testing::{Header, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+pub(crate) const INITIAL_BALANCE: Balance = 100;
+pub(crate) const CANDIDACY_BOND: Balance = 10;
+pub(crate) const SESSION_PERIOD: BlockNumber = 10;
+pub(crate) const COOLDOWN: u32 = 2;
+
+/// Invulnerable collator of genesis validator set.
+pub(crate) const INVULNERABLE: AccountId = 1;
+/// Account with session keys that isn't a candidate at genesis.
+pub(crate) const COLLATOR: AccountId = 2;
+/// Account without session keys.
+pub(crate) const NOBODY: AccountId = 3;
+
+construct_runtime!(
+    pub enum TestRuntime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>},
+        Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+        CollatorExit: pallet_collator_exit::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Origin = Origin;
+    type Index = u64;
+    type Call = Call;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const MaxLocks: u32 = 4;
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const SessionPeriod: BlockNumber = SESSION_PERIOD;
+    pub const SessionOffset: BlockNumber = 0;
+}
+
+impl pallet_session::Config for TestRuntime {
+    type Event = Event;
+    type ValidatorId = <Self as frame_system::Config>::AccountId;
+    type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+    type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type SessionManager = CollatorSelection;
+    type SessionHandler = pallet_session::TestSessionHandler;
+    type Keys = UintAuthorityId;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const PotId: PalletId = PalletId(*b"PotStake");
+    pub const MaxCandidates: u32 = 20;
+    pub const MinCandidates: u32 = 0;
+    pub const MaxInvulnerables: u32 = 20;
+    // Candidates shouldn't be kicked during tests
+    pub const KickThreshold: BlockNumber = 1_000;
+}
+
+impl pallet_collator_selection::Config for TestRuntime {
+    type Event = Event;
+    type Currency = Balances;
+    type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+    type PotId = PotId;
+    type MaxCandidates = MaxCandidates;
+    type MinCandidates = MinCandidates;
+    type MaxInvulnerables = MaxInvulnerables;
+    type KickThreshold = KickThreshold;
+    type ValidatorId = <Self as frame_system::Config>::AccountId;
+    type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+    type ValidatorRegistration = Session;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const Cooldown: u32 = COOLDOWN;
+}
+
+impl pallet_collator_exit::Config for TestRuntime {
+    type Event = Event;
+    type Cooldown = Cooldown;
+}
+
+pub struct ExternalityBuilder;
+
+impl ExternalityBuilder {
+    pub fn build() -> TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<TestRuntime>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<TestRuntime> {
+            balances: vec![
+                (INVULNERABLE, INITIAL_BALANCE),
+                (COLLATOR, INITIAL_BALANCE),
+                (NOBODY, INITIAL_BALANCE),
+            ],
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        pallet_collator_selection::GenesisConfig::<TestRuntime> {
+            invulnerables: vec![INVULNERABLE],
+            candidacy_bond: CANDIDACY_BOND,
+            desired_candidates: 2,
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        pallet_session::GenesisConfig::<TestRuntime> {
+            keys: [INVULNERABLE, COLLATOR]
+                .iter()
+                .map(|who| (*who, *who, UintAuthorityId(*who)))
+                .collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
+
+/// Used to run to the specified block number
+pub fn run_to_block(n: BlockNumber) {
+    while System::block_number() < n {
+        Session::on_finalize(System::block_number());
+        System::set_block_number(System::block_number() + 1);
+        Session::on_initialize(System::block_number());
+    }
+}
+
+/// Used to get a vec of all collator exit events
+pub fn collator_exit_events() -> Vec<crate::Event<TestRuntime>> {
+    System::events()
+        .into_iter()
+        .map(|r| r.event)
+        .filter_map(|e| {
+            if let Event::CollatorExit(inner) = e {
+                Some(inner)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
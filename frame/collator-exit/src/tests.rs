@@ -0,0 +1,136 @@
+use super::{pallet::Error, Event, *};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{LockableCurrency, WithdrawReasons},
+};
+use mock::*;
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn leave_intent_keeps_bond_for_cooldown() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        assert_eq!(Balances::reserved_balance(&COLLATOR), CANDIDACY_BOND);
+
+        assert_ok!(CollatorExit::leave_intent(Origin::signed(COLLATOR)));
+
+        // Candidacy is left at once, the bond is still reserved
+        assert!(CollatorSelection::candidates().is_empty());
+        assert_eq!(Balances::reserved_balance(&COLLATOR), CANDIDACY_BOND);
+        assert_eq!(
+            CollatorExit::leaving(&COLLATOR),
+            Some(Unbonding {
+                amount: CANDIDACY_BOND,
+                release_session: COOLDOWN,
+            })
+        );
+        assert_eq!(
+            collator_exit_events(),
+            vec![Event::LeaveIntent(COLLATOR, CANDIDACY_BOND, COOLDOWN)]
+        );
+
+        // Cooldown isn't elapsed in the next session
+        run_to_block(SESSION_PERIOD);
+        assert_eq!(Session::current_index(), 1);
+        assert_noop!(
+            CollatorExit::withdraw_bond(Origin::signed(COLLATOR)),
+            Error::<TestRuntime>::CooldownNotElapsed
+        );
+
+        run_to_block(2 * SESSION_PERIOD);
+        assert_eq!(Session::current_index(), 2);
+        assert_ok!(CollatorExit::withdraw_bond(Origin::signed(COLLATOR)));
+
+        assert_eq!(Balances::reserved_balance(&COLLATOR), 0);
+        assert_eq!(Balances::free_balance(&COLLATOR), INITIAL_BALANCE);
+        assert_eq!(CollatorExit::leaving(&COLLATOR), None);
+        assert_eq!(
+            collator_exit_events().last(),
+            Some(&Event::BondReleased(COLLATOR, CANDIDACY_BOND))
+        );
+    })
+}
+
+#[test]
+fn leave_intent_is_reverted_when_bond_can_not_be_reserved() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        Balances::set_lock(*b"testlock", &COLLATOR, INITIAL_BALANCE, WithdrawReasons::all());
+
+        // Collator stays a candidate with the bond reserved by collator selection
+        assert_noop!(
+            CollatorExit::leave_intent(Origin::signed(COLLATOR)),
+            pallet_balances::Error::<TestRuntime>::LiquidityRestrictions
+        );
+        assert_eq!(CollatorSelection::candidates().len(), 1);
+        assert_eq!(Balances::reserved_balance(&COLLATOR), CANDIDACY_BOND);
+        assert_eq!(CollatorExit::leaving(&COLLATOR), None);
+
+        Balances::remove_lock(*b"testlock", &COLLATOR);
+        assert_ok!(CollatorExit::leave_intent(Origin::signed(COLLATOR)));
+        assert!(CollatorSelection::candidates().is_empty());
+    })
+}
+
+#[test]
+fn leave_intent_is_checked() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_noop!(CollatorExit::leave_intent(Origin::root()), BadOrigin);
+        assert_noop!(
+            CollatorExit::leave_intent(Origin::signed(NOBODY)),
+            Error::<TestRuntime>::NotCandidate
+        );
+        // Invulnerables aren't candidates
+        assert_noop!(
+            CollatorExit::leave_intent(Origin::signed(INVULNERABLE)),
+            Error::<TestRuntime>::NotCandidate
+        );
+
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        assert_ok!(CollatorExit::leave_intent(Origin::signed(COLLATOR)));
+
+        // Registered again, but the previous bond isn't withdrawn yet
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        assert_eq!(Balances::reserved_balance(&COLLATOR), 2 * CANDIDACY_BOND);
+        assert_noop!(
+            CollatorExit::leave_intent(Origin::signed(COLLATOR)),
+            Error::<TestRuntime>::AlreadyLeaving
+        );
+    })
+}
+
+#[test]
+fn withdraw_bond_is_checked() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_noop!(CollatorExit::withdraw_bond(Origin::root()), BadOrigin);
+        assert_noop!(
+            CollatorExit::withdraw_bond(Origin::signed(COLLATOR)),
+            Error::<TestRuntime>::NotLeaving
+        );
+
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        assert_ok!(CollatorExit::leave_intent(Origin::signed(COLLATOR)));
+        assert_noop!(
+            CollatorExit::withdraw_bond(Origin::signed(COLLATOR)),
+            Error::<TestRuntime>::CooldownNotElapsed
+        );
+
+        // Only the leaving bond is released
+        run_to_block(2 * SESSION_PERIOD);
+        assert_ok!(CollatorSelection::register_as_candidate(Origin::signed(
+            COLLATOR
+        )));
+        assert_ok!(CollatorExit::withdraw_bond(Origin::signed(COLLATOR)));
+        assert_eq!(Balances::reserved_balance(&COLLATOR), CANDIDACY_BOND);
+    })
+}
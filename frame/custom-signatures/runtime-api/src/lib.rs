@@ -0,0 +1,17 @@
+//! Runtime API definition for the custom signatures pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+    pub trait CustomSignaturesApi<Call, Balance> where
+        Call: Codec,
+        Balance: Codec,
+    {
+        /// Fee withdrawn from signer before `call` dispatch.
+        ///
+        /// Note: part of the fee is refunded when actual weight of `call` is lower.
+        fn query_call_fee(call: Call) -> Balance;
+    }
+}
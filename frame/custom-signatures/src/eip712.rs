@@ -0,0 +1,175 @@
+//! EIP-712 typed structured data signatures.
+//!
+//! Payload signed by `personal_sign` is just an opaque hash for wallet user. Typed data
+//! signature makes wallet to display the call fields before signing:
+//! ```nocompile
+//! EIP712Domain(string name,uint256 chainId,address verifyingContract)
+//! EthCall(uint8 pallet,uint8 call,bytes args,uint32 nonce,uint256 fee)
+//! ```
+//! where `verifyingContract` is an address with custom signatures pallet index in the lowest
+//! byte, `pallet` and `call` are indexes of dispatched call and `args` is SCALE-encoded
+//! call arguments. Sponsor account of `sponsored_call` and `PayloadVersion::V1` replay
+//! protection fields follow call arguments.
+//! Batch payload isn't a call, so its `pallet` and `call` fields are meaningless, but `fee`
+//! is the fee of the whole batch.
+
+use crate::{ethereum::EthereumSignature, CheckSignature, SignatureFailure};
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, U256};
+use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, hashing::keccak_256};
+use sp_runtime::traits::{IdentifyAccount, Lazy, Verify};
+use sp_runtime::{AccountId32, MultiSignature, MultiSigner};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
+
+/// EIP-712 domain type.
+pub const DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,uint256 chainId,address verifyingContract)";
+
+/// Signed call type.
+pub const ETH_CALL_TYPE: &[u8] =
+    b"EthCall(uint8 pallet,uint8 call,bytes args,uint32 nonce,uint256 fee)";
+
+/// Runtime specific parameters of typed data.
+pub trait Eip712Domain {
+    /// Signing domain name, e.g. network name.
+    fn name() -> Vec<u8>;
+    /// EIP-155 chain identifier.
+    fn chain_id() -> u64;
+    /// Index of custom signatures pallet in the runtime.
+    fn pallet_index() -> u8;
+    /// Fee withdrawn for dispatch of SCALE-encoded call or `(calls, atomic)` batch,
+    /// e.g. `Pallet::query_payload_fee`.
+    ///
+    /// Note: encoded call could be followed by other payload fields.
+    fn call_fee(call: &[u8]) -> u128;
+}
+
+fn u256_word(value: U256) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    word
+}
+
+/// EIP-712 domain separator.
+pub fn domain_separator<D: Eip712Domain>() -> [u8; 32] {
+    let mut data = keccak_256(DOMAIN_TYPE).to_vec();
+    data.extend_from_slice(&keccak_256(&D::name()[..]));
+    data.extend_from_slice(&u256_word(D::chain_id().into()));
+    // Left padded address is the same as uint256 of pallet index
+    data.extend_from_slice(&u256_word(D::pallet_index().into()));
+    keccak_256(&data[..])
+}
+
+/// Constructs the message hash that `eth_signTypedData_v4` would sign for the pallet payload.
+///
+/// Payload is SCALE-encoded `(CallMagicNumber, nonce, call)` with `u32` nonce, `None` is
+/// returned for malformed payload. Magic number isn't signed, chain id is used instead.
+pub fn signable_message<D: Eip712Domain>(payload: &[u8]) -> Option<[u8; 32]> {
+    let mut input = payload;
+    let (_magic, nonce) = <(u16, u32)>::decode(&mut input).ok()?;
+    if input.len() < 2 {
+        return None;
+    }
+
+    let mut data = keccak_256(ETH_CALL_TYPE).to_vec();
+    data.extend_from_slice(&u256_word(input[0].into()));
+    data.extend_from_slice(&u256_word(input[1].into()));
+    data.extend_from_slice(&keccak_256(&input[2..]));
+    data.extend_from_slice(&u256_word(nonce.into()));
+    data.extend_from_slice(&u256_word(D::call_fee(input).into()));
+    let struct_hash = keccak_256(&data[..]);
+
+    let mut message = b"\x19\x01".to_vec();
+    message.extend_from_slice(&domain_separator::<D>());
+    message.extend_from_slice(&struct_hash);
+    Some(keccak_256(&message[..]))
+}
+
+/// EIP-712 typed data signature.
+///
+/// Signatures made by `personal_sign` are accepted as well, see `EthereumSignature`.
+#[derive(Encode, Decode, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(D))]
+pub struct Eip712Signature<D>(pub [u8; 65], PhantomData<D>);
+
+impl<D> Eip712Signature<D> {
+    /// Create signature from raw bytes.
+    pub fn new(signature: [u8; 65]) -> Self {
+        Self(signature, PhantomData)
+    }
+}
+
+impl<D> Clone for Eip712Signature<D> {
+    fn clone(&self) -> Self {
+        Self::new(self.0)
+    }
+}
+
+impl<D> PartialEq for Eip712Signature<D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl<D> Eq for Eip712Signature<D> {}
+
+impl<D> sp_std::fmt::Debug for Eip712Signature<D> {
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+        write!(f, "Eip712Signature({:?})", &self.0[..])
+    }
+}
+
+impl<D> From<ecdsa::Signature> for Eip712Signature<D> {
+    fn from(signature: ecdsa::Signature) -> Self {
+        Self::new(signature.into())
+    }
+}
+
+impl<D> TryFrom<Vec<u8>> for Eip712Signature<D> {
+    type Error = ();
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        EthereumSignature::try_from(data).map(|signature| Self::new(signature.0))
+    }
+}
+
+impl<D: Eip712Domain> Eip712Signature<D> {
+    /// Recover signer account of the typed data made for the pallet payload.
+    pub fn recover(&self, payload: &[u8]) -> Option<AccountId32> {
+        let hash = signable_message::<D>(payload)?;
+        secp256k1_ecdsa_recover_compressed(&self.0, &hash)
+            .ok()
+            .map(|public| MultiSigner::from(ecdsa::Public::from_raw(public)).into_account())
+    }
+}
+
+/// Typed data signature is checked first, `personal_sign` message is checked when it fails.
+impl<D: Eip712Domain> CheckSignature<AccountId32> for Eip712Signature<D> {
+    fn check(&self, msg: &[u8], signer: &AccountId32) -> Result<(), SignatureFailure> {
+        let typed_data_signer = self.recover(msg);
+        if typed_data_signer.as_ref() == Some(signer) {
+            return Ok(());
+        }
+
+        match EthereumSignature(self.0).check(msg, signer) {
+            Err(SignatureFailure::Unrecoverable) if typed_data_signer.is_some() => {
+                Err(SignatureFailure::SignerMismatch)
+            }
+            res => res,
+        }
+    }
+}
+
+/// Attempts to recover the Ethereum public key from a typed data signature,
+/// falls back to `personal_sign` message when it fails.
+impl<D: Eip712Domain> Verify for Eip712Signature<D> {
+    type Signer = <MultiSignature as Verify>::Signer;
+
+    fn verify<L: Lazy<[u8]>>(
+        &self,
+        mut msg: L,
+        account: &<Self::Signer as IdentifyAccount>::AccountId,
+    ) -> bool {
+        self.check(msg.get(), account).is_ok()
+    }
+}
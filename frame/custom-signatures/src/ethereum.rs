@@ -1,10 +1,11 @@
 //! Ethereum prefixed signatures compatibility instances.
 
+use crate::{CheckSignature, SignatureFailure};
 use codec::{Decode, Encode};
 use sp_core::ecdsa;
 use sp_io::{crypto::secp256k1_ecdsa_recover_compressed, hashing::keccak_256};
 use sp_runtime::traits::{IdentifyAccount, Lazy, Verify};
-use sp_runtime::MultiSignature;
+use sp_runtime::{AccountId32, MultiSignature, MultiSigner};
 use sp_std::prelude::*;
 
 /// Ethereum-compatible signature type.
@@ -47,6 +48,27 @@ pub fn signable_message(what: &[u8]) -> Vec<u8> {
     v
 }
 
+impl EthereumSignature {
+    /// Recover signer account of the message signed by using the Ethereum RPC's
+    /// `personal_sign` and `eth_sign`.
+    pub fn recover(&self, msg: &[u8]) -> Option<AccountId32> {
+        let msg = keccak_256(&signable_message(msg));
+        secp256k1_ecdsa_recover_compressed(&self.0, &msg)
+            .ok()
+            .map(|public| MultiSigner::from(ecdsa::Public::from_raw(public)).into_account())
+    }
+}
+
+impl CheckSignature<AccountId32> for EthereumSignature {
+    fn check(&self, msg: &[u8], signer: &AccountId32) -> Result<(), SignatureFailure> {
+        match self.recover(msg) {
+            Some(account) if account == *signer => Ok(()),
+            Some(_) => Err(SignatureFailure::SignerMismatch),
+            None => Err(SignatureFailure::Unrecoverable),
+        }
+    }
+}
+
 /// Attempts to recover the Ethereum public key from a message signature signed by using
 /// the Ethereum RPC's `personal_sign` and `eth_sign`.
 impl Verify for EthereumSignature {
@@ -57,14 +79,7 @@ impl Verify for EthereumSignature {
         mut msg: L,
         account: &<Self::Signer as IdentifyAccount>::AccountId,
     ) -> bool {
-        let msg = keccak_256(&signable_message(msg.get()));
-        match secp256k1_ecdsa_recover_compressed(&self.0, &msg).ok() {
-            Some(public) => {
-                let signer = Self::Signer::from(ecdsa::Public::from_raw(public));
-                *account == signer.into_account()
-            }
-            None => false,
-        }
+        self.check(msg.get(), account).is_ok()
     }
 }
 
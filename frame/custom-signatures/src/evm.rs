@@ -0,0 +1,195 @@
+//! EVM-address-native accounts for Ethereum signers.
+//!
+//! `EthereumSignature` derives signer account from the compressed public key as `MultiSigner`
+//! does, but EVM uses account derived from the Ethereum address. `EvmSignature` checks signer
+//! account derived by the EVM `AddressMapping`, so the same key controls the same balance
+//! on both sides. Funds of the legacy account could be moved by `LegacyToEvmAccount` claim.
+
+use crate::{
+    eip712::{self, Eip712Domain, Eip712Signature},
+    ethereum::{self, EthereumSignature},
+    CheckSignature, SignatureFailure,
+};
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, H160};
+use sp_io::{
+    crypto::{secp256k1_ecdsa_recover, secp256k1_ecdsa_recover_compressed},
+    hashing::keccak_256,
+};
+use sp_runtime::traits::{IdentifyAccount, Lazy, Verify};
+use sp_runtime::{AccountId32, MultiSigner};
+use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
+
+/// Ethereum address to account mapping, should be the same as EVM `AddressMapping`.
+pub trait AddressMapping<AccountId> {
+    /// Account of the Ethereum address.
+    fn into_account_id(address: H160) -> AccountId;
+}
+
+/// Ethereum address of uncompressed public key.
+pub fn public_to_address(public: &[u8; 64]) -> H160 {
+    H160::from_slice(&keccak_256(public)[12..])
+}
+
+/// Recover Ethereum addresses from the signature.
+pub trait RecoverAddress {
+    /// Addresses that could sign `msg`, empty when signer can't be recovered.
+    fn recover_addresses(&self, msg: &[u8]) -> Vec<H160>;
+
+    /// Check signature of the scheme without Ethereum address against `signer` account,
+    /// `None` for Ethereum signatures.
+    fn check_native(
+        &self,
+        _msg: &[u8],
+        _signer: &AccountId32,
+    ) -> Option<Result<(), SignatureFailure>> {
+        None
+    }
+}
+
+impl RecoverAddress for EthereumSignature {
+    fn recover_addresses(&self, msg: &[u8]) -> Vec<H160> {
+        let hash = keccak_256(&ethereum::signable_message(msg));
+        secp256k1_ecdsa_recover(&self.0, &hash)
+            .ok()
+            .map(|public| public_to_address(&public))
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<D: Eip712Domain> RecoverAddress for Eip712Signature<D> {
+    fn recover_addresses(&self, msg: &[u8]) -> Vec<H160> {
+        let mut addresses: Vec<H160> = eip712::signable_message::<D>(msg)
+            .and_then(|hash| secp256k1_ecdsa_recover(&self.0, &hash).ok())
+            .map(|public| public_to_address(&public))
+            .into_iter()
+            .collect();
+        addresses.extend(EthereumSignature(self.0).recover_addresses(msg));
+        addresses
+    }
+}
+
+/// Signer identified by Ethereum address.
+pub struct EvmSigner<M>(pub H160, PhantomData<M>);
+
+impl<M> From<H160> for EvmSigner<M> {
+    fn from(address: H160) -> Self {
+        Self(address, PhantomData)
+    }
+}
+
+impl<M: AddressMapping<AccountId32>> IdentifyAccount for EvmSigner<M> {
+    type AccountId = AccountId32;
+
+    fn into_account(self) -> AccountId32 {
+        M::into_account_id(self.0)
+    }
+}
+
+/// Ethereum signature `S` checked against account mapped from Ethereum address by `M`.
+#[derive(Encode, Decode, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(M))]
+pub struct EvmSignature<S, M>(pub S, PhantomData<M>);
+
+impl<S, M> EvmSignature<S, M> {
+    /// Wrap Ethereum signature.
+    pub fn new(signature: S) -> Self {
+        Self(signature, PhantomData)
+    }
+}
+
+impl<S: Clone, M> Clone for EvmSignature<S, M> {
+    fn clone(&self) -> Self {
+        Self::new(self.0.clone())
+    }
+}
+
+impl<S: PartialEq, M> PartialEq for EvmSignature<S, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<S: Eq, M> Eq for EvmSignature<S, M> {}
+
+impl<S: sp_std::fmt::Debug, M> sp_std::fmt::Debug for EvmSignature<S, M> {
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+        write!(f, "EvmSignature({:?})", self.0)
+    }
+}
+
+impl<S: TryFrom<Vec<u8>>, M> TryFrom<Vec<u8>> for EvmSignature<S, M> {
+    type Error = S::Error;
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        S::try_from(data).map(Self::new)
+    }
+}
+
+impl<S, M> CheckSignature<AccountId32> for EvmSignature<S, M>
+where
+    S: RecoverAddress,
+    M: AddressMapping<AccountId32>,
+{
+    fn check(&self, msg: &[u8], signer: &AccountId32) -> Result<(), SignatureFailure> {
+        if let Some(res) = self.0.check_native(msg, signer) {
+            return res;
+        }
+
+        let addresses = self.0.recover_addresses(msg);
+        if addresses.is_empty() {
+            Err(SignatureFailure::Unrecoverable)
+        } else if addresses
+            .into_iter()
+            .any(|address| M::into_account_id(address) == *signer)
+        {
+            Ok(())
+        } else {
+            Err(SignatureFailure::SignerMismatch)
+        }
+    }
+}
+
+impl<S, M> Verify for EvmSignature<S, M>
+where
+    S: RecoverAddress,
+    M: AddressMapping<AccountId32>,
+{
+    type Signer = EvmSigner<M>;
+
+    fn verify<L: Lazy<[u8]>>(&self, mut msg: L, account: &AccountId32) -> bool {
+        self.check(msg.get(), account).is_ok()
+    }
+}
+
+/// Claim of accounts controlled by the same key.
+pub trait ClaimAccount<AccountId> {
+    /// Accounts `(from, to)` controlled by signer of `msg`.
+    ///
+    /// Returns `None` when signature is invalid or claims aren't supported.
+    fn claim(signature: &[u8], msg: &[u8]) -> Option<(AccountId, AccountId)>;
+}
+
+impl<AccountId> ClaimAccount<AccountId> for () {
+    fn claim(_signature: &[u8], _msg: &[u8]) -> Option<(AccountId, AccountId)> {
+        None
+    }
+}
+
+/// Claim funds of the account derived by `MultiSigner` to the account mapped by `M`.
+///
+/// Claim message should be signed by `personal_sign`.
+pub struct LegacyToEvmAccount<M>(PhantomData<M>);
+
+impl<M: AddressMapping<AccountId32>> ClaimAccount<AccountId32> for LegacyToEvmAccount<M> {
+    fn claim(signature: &[u8], msg: &[u8]) -> Option<(AccountId32, AccountId32)> {
+        let signature = EthereumSignature::try_from(signature.to_vec()).ok()?;
+        let hash = keccak_256(&ethereum::signable_message(msg));
+        let compressed = secp256k1_ecdsa_recover_compressed(&signature.0, &hash).ok()?;
+        let public = secp256k1_ecdsa_recover(&signature.0, &hash).ok()?;
+
+        let legacy = MultiSigner::from(ecdsa::Public::from_raw(compressed)).into_account();
+        Some((legacy, M::into_account_id(public_to_address(&public))))
+    }
+}
@@ -1,25 +1,84 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{generic::Era, RuntimeDebug};
+
 pub use pallet::*;
 
 /// Ethereum-compatible signatures (eth_sign API call).
 pub mod ethereum;
 
+/// EIP-712 typed data signatures (eth_signTypedData_v4 API call).
+pub mod eip712;
+
+/// Ethereum signers with accounts mapped from Ethereum address like EVM does.
+pub mod evm;
+
+/// Signatures of several schemes selected by tag byte.
+pub mod multi;
+
+/// Off-chain payload building and signing.
+#[cfg(feature = "std")]
+pub mod signer;
+
 #[cfg(test)]
 mod tests;
 
+/// Reason of signature check failure.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SignatureFailure {
+    /// Signer can't be recovered from the signature.
+    Unrecoverable,
+    /// Signature is made by other account.
+    SignerMismatch,
+}
+
+/// Signature verification that reports the failure reason.
+pub trait CheckSignature<AccountId> {
+    /// Check that `msg` is signed by `signer`.
+    fn check(&self, msg: &[u8], signer: &AccountId) -> Result<(), SignatureFailure>;
+}
+
+/// Fees sponsorship of the account.
+#[derive(Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct Sponsorship<Balance> {
+    /// Amount of fees the sponsor still agrees to pay.
+    pub allowance: Balance,
+    /// Fees of `SponsoredCalls` are paid without sponsor signature.
+    pub open: bool,
+}
+
+/// Version of signed call payload.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum PayloadVersion {
+    /// `(CallMagicNumber, nonce, call)`, could be replayed on network with the same magic number.
+    V0,
+    /// `(CallMagicNumber, nonce, call, genesis_hash, era, era_block_hash)`, the same checks
+    /// as `CheckGenesis` and `CheckEra` signed extensions do.
+    V1(Era),
+}
+
 #[frame_support::pallet]
 pub mod pallet {
+    use super::{
+        evm::ClaimAccount, CheckSignature, PayloadVersion, SignatureFailure, Sponsorship,
+    };
+    use codec::DecodeAll;
     use frame_support::{
         pallet_prelude::*,
         traits::{
-            Currency, ExistenceRequirement, Get, OnUnbalanced, UnfilteredDispatchable,
-            WithdrawReasons,
+            tokens::fungible, Contains, Currency, ExistenceRequirement, Get, Imbalance,
+            OnUnbalanced, UnfilteredDispatchable, WithdrawReasons,
         },
-        weights::GetDispatchInfo,
+        storage::{with_transaction, TransactionOutcome},
+        weights::{GetDispatchInfo, PostDispatchInfo, WeightToFeePolynomial},
     };
     use frame_system::{ensure_none, pallet_prelude::*};
-    use sp_runtime::traits::{IdentifyAccount, Verify};
+    use sp_runtime::{
+        generic::Era,
+        traits::{IdentifyAccount, One, SaturatedConversion, Saturating, Verify, Zero},
+    };
     use sp_std::{convert::TryFrom, prelude::*};
 
     #[pallet::pallet]
@@ -38,53 +97,96 @@ pub mod pallet {
         type Call: Parameter + UnfilteredDispatchable<Origin = Self::Origin> + GetDispatchInfo;
 
         /// User defined signature type.
-        type Signature: Parameter + Verify<Signer = Self::Signer> + TryFrom<Vec<u8>>;
+        type Signature: Parameter
+            + Verify<Signer = Self::Signer>
+            + CheckSignature<Self::AccountId>
+            + TryFrom<Vec<u8>>;
 
         /// User defined signer type.
         type Signer: IdentifyAccount<AccountId = Self::AccountId>;
 
-        /// The currency trait.
-        type Currency: Currency<Self::AccountId>;
+        /// The currency trait, `fungible::Inspect` gives the transferable balance of claims.
+        type Currency: Currency<Self::AccountId> + fungible::Inspect<Self::AccountId>;
 
         /// The call fee destination.
         type OnChargeTransaction: OnUnbalanced<
             <Self::Currency as Currency<Self::AccountId>>::NegativeImbalance,
         >;
 
-        /// The call processing fee amount.
+        /// Convert a weight value into a deductible fee based on the currency type.
+        type WeightToFee: WeightToFeePolynomial<Balance = BalanceOf<Self>>;
+
+        /// The fee to be paid for making a call, per byte of encoded call.
         #[pallet::constant]
-        type CallFee: Get<BalanceOf<Self>>;
+        type TransactionByteFee: Get<BalanceOf<Self>>;
 
         /// The call magic number.
         #[pallet::constant]
         type CallMagicNumber: Get<u16>;
 
+        /// Accounts claim handler, `()` disables claims.
+        type ClaimAccount: ClaimAccount<Self::AccountId>;
+
+        /// Calls which fees could be paid by open sponsor without its signature.
+        type SponsoredCalls: Contains<<Self as Config>::Call>;
+
         /// A configuration for base priority of unsigned transactions.
         ///
         /// This is exposed so that it can be tuned for particular runtime, when
-        /// multiple pallets send unsigned transactions.
+        /// multiple pallets send unsigned transactions. Priority of the paid fee is
+        /// added to this base value.
         type UnsignedPriority: Get<TransactionPriority>;
     }
 
     #[pallet::error]
     pub enum Error<T> {
-        /// Signature decode fails.
+        /// Signature decode fails, e.g. wrong signature length.
         DecodeFailure,
         /// Signature and account mismatched.
         InvalidSignature,
         /// Bad nonce parameter.
         BadNonce,
+        /// Era birth block hash is unknown.
+        AncientBirthBlock,
+        /// Signer can't be recovered from the signature.
+        UnrecoverableSignature,
+        /// Signature is made for payload of other version.
+        PayloadVersionMismatch,
+        /// Account claim signature is invalid or claims are disabled.
+        InvalidClaim,
+        /// Claimed account has nothing to move.
+        NothingToClaim,
+        /// Sponsor signature is invalid or sponsor doesn't pay for the call without it.
+        NotSponsored,
+        /// Fee is above the sponsor allowance.
+        SponsorAllowanceExceeded,
+        /// Batch has no calls.
+        EmptyBatch,
     }
 
+    /// Fees sponsorship of accounts.
+    #[pallet::storage]
+    #[pallet::getter(fn sponsorship)]
+    pub type Sponsorships<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Sponsorship<BalanceOf<T>>, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
-        /// A call just executed. \[result\]
+        /// A call just executed. \[signer, result\]
         Executed(T::AccountId, DispatchResult),
+        /// Fee for signed calls is paid. \[signer, payer, actual_weight, fee\]
+        FeePaid(T::AccountId, T::AccountId, Weight, BalanceOf<T>),
+        /// Fees sponsorship is set. \[sponsor, allowance, open\]
+        SponsorshipSet(T::AccountId, BalanceOf<T>, bool),
+        /// Funds of claimed account are moved. \[from, to, amount\]
+        AccountClaimed(T::AccountId, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Dispatch `call` signed by `signer` using `PayloadVersion::V0` payload.
+        ///
         /// # <weight>
         /// - O(1).
         /// - Limited storage reads.
@@ -103,7 +205,201 @@ pub mod pallet {
             #[pallet::compact] nonce: T::Index,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            let payloads = Self::payloads(&*call, &nonce, &PayloadVersion::V0);
+            Self::do_dispatch(vec![*call], false, signer, signature, nonce, payloads, None)
+        }
 
+        /// Dispatch `call` signed by `signer` using payload of given `version`.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - Limited storage reads.
+        /// - One DB write (event).
+        /// - Weight of derivative `call` execution + 10,000.
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (dispatch_info.weight + 10_000, dispatch_info.class)
+        })]
+        pub fn versioned_call(
+            origin: OriginFor<T>,
+            call: Box<<T as Config>::Call>,
+            signer: T::AccountId,
+            signature: Vec<u8>,
+            version: PayloadVersion,
+            #[pallet::compact] nonce: T::Index,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let payloads = Self::payloads(&*call, &nonce, &version);
+            Self::do_dispatch(vec![*call], false, signer, signature, nonce, payloads, None)
+        }
+
+        /// Dispatch `call` signed by `signer`, the fee is paid by `sponsor`.
+        ///
+        /// Signer signs `(call, sponsor)` instead of `call` in the versioned payload, so
+        /// the signature can't be dispatched without sponsor or with other sponsor.
+        /// Sponsor should sign `sponsor_payload` of the signer payload. Without signature
+        /// sponsorship should be open and `call` should be one of `SponsoredCalls`.
+        /// The fee is limited by sponsor allowance.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - Limited storage reads.
+        /// - Two DB writes (events, allowance).
+        /// - Weight of derivative `call` execution + 10,000.
+        /// # </weight>
+        #[pallet::weight({
+            let dispatch_info = call.get_dispatch_info();
+            (dispatch_info.weight + 10_000, dispatch_info.class)
+        })]
+        pub fn sponsored_call(
+            origin: OriginFor<T>,
+            call: Box<<T as Config>::Call>,
+            signer: T::AccountId,
+            signature: Vec<u8>,
+            version: PayloadVersion,
+            #[pallet::compact] nonce: T::Index,
+            sponsor: T::AccountId,
+            sponsor_signature: Option<Vec<u8>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            let payloads = Self::payloads(&(&*call, &sponsor), &nonce, &version);
+            let sponsor = Some((sponsor, sponsor_signature));
+            Self::do_dispatch(vec![*call], false, signer, signature, nonce, payloads, sponsor)
+        }
+
+        /// Set fees sponsorship of the origin account.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - One DB write (sponsorship).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(T::DbWeight::get().writes(2).saturating_add(10_000_000))]
+        pub fn set_sponsorship(
+            origin: OriginFor<T>,
+            allowance: BalanceOf<T>,
+            open: bool,
+        ) -> DispatchResultWithPostInfo {
+            let sponsor = ensure_signed(origin)?;
+
+            if allowance.is_zero() {
+                Sponsorships::<T>::remove(&sponsor);
+            } else {
+                Sponsorships::<T>::insert(&sponsor, Sponsorship { allowance, open });
+            }
+            Self::deposit_event(Event::SponsorshipSet(sponsor, allowance, open));
+
+            Ok(().into())
+        }
+
+        /// Dispatch batch of `calls` signed by `signer` with one signature.
+        ///
+        /// Calls are dispatched one by one, each emits `Executed` event. Dispatch stops at the
+        /// first failed call. When `atomic` is set changes of all calls are reverted in case of
+        /// failure, so only `Executed` event of the failed call remains.
+        ///
+        /// Signed payload is `(CallMagicNumber, nonce, calls, atomic)` followed by
+        /// replay protection fields of `version`.
+        ///
+        /// # <weight>
+        /// - O(C) where C is the number of calls.
+        /// - Limited storage reads.
+        /// - One DB write (event) per call.
+        /// - Weight of derivative `calls` execution + 10,000.
+        /// # </weight>
+        #[pallet::weight({
+            let weight = calls
+                .iter()
+                .map(|call| call.get_dispatch_info().weight)
+                .fold(10_000 as Weight, |total, weight| total.saturating_add(weight));
+            (weight, DispatchClass::Normal)
+        })]
+        pub fn call_batch(
+            origin: OriginFor<T>,
+            calls: Vec<<T as Config>::Call>,
+            atomic: bool,
+            signer: T::AccountId,
+            signature: Vec<u8>,
+            version: PayloadVersion,
+            #[pallet::compact] nonce: T::Index,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            ensure!(!calls.is_empty(), Error::<T>::EmptyBatch);
+            let payloads = Self::payloads(&(&calls[..], atomic), &nonce, &version);
+            Self::do_dispatch(calls, atomic, signer, signature, nonce, payloads, None)
+        }
+
+        /// Move all transferable funds of the account claimed by `signature` to the linked
+        /// account `to`, e.g. from account of Ethereum public key to account of Ethereum address.
+        /// Locked funds, e.g. vesting, are left on the claimed account.
+        ///
+        /// Signed payload is `(CallMagicNumber, b"claim", genesis_hash, to, nonce)`, where
+        /// `nonce` is the current nonce of `to` account, it's incremented by the claim.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - Three DB reads and writes (balances and nonce).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 4).saturating_add(50_000_000))]
+        pub fn claim_account(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            signature: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+
+            let (from, amount) = Self::do_claim(&to, &signature)?;
+            Self::deposit_event(Event::AccountClaimed(from, to, amount));
+
+            Ok(Pays::No.into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Move all transferable funds of the account claimed by `signature` to the linked
+        /// account `to`.
+        ///
+        /// Signature of `claim_payload` is checked by `ClaimAccount` handler.
+        fn do_claim(
+            to: &T::AccountId,
+            signature: &[u8],
+        ) -> Result<(T::AccountId, BalanceOf<T>), DispatchError> {
+            let nonce = frame_system::Pallet::<T>::account_nonce(to);
+            let payload = Self::claim_payload(to, &nonce);
+            let from = T::ClaimAccount::claim(signature, &payload[..])
+                .filter(|(from, linked)| from != to && linked == to)
+                .map(|(from, _)| from)
+                .ok_or(Error::<T>::InvalidClaim)?;
+            // Locked and reserved funds are left on the claimed account
+            let amount: BalanceOf<T> =
+                <T::Currency as fungible::Inspect<T::AccountId>>::reducible_balance(&from, false)
+                    .saturated_into::<u128>()
+                    .saturated_into();
+            ensure!(!amount.is_zero(), Error::<T>::NothingToClaim);
+
+            T::Currency::transfer(&from, to, amount, ExistenceRequirement::AllowDeath)?;
+            frame_system::Pallet::<T>::inc_account_nonce(to);
+            Ok((from, amount))
+        }
+
+        /// Encoded payload of accounts claim to `to` account with its current `nonce`.
+        pub fn claim_payload(to: &T::AccountId, nonce: &T::Index) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            (T::CallMagicNumber::get(), b"claim", genesis_hash, to, nonce).encode()
+        }
+
+        /// Check signature, charge fee and dispatch `calls` from `signer` origin.
+        fn do_dispatch(
+            calls: Vec<<T as Config>::Call>,
+            atomic: bool,
+            signer: T::AccountId,
+            signature: Vec<u8>,
+            nonce: T::Index,
+            payloads: Result<(Vec<u8>, Option<Vec<u8>>), InvalidTransaction>,
+            sponsor: Option<(T::AccountId, Option<Vec<u8>>)>,
+        ) -> DispatchResultWithPostInfo {
             // Ensure that transaction isn't stale
             ensure!(
                 nonce == frame_system::Pallet::<T>::account_nonce(signer.clone()),
@@ -113,87 +409,509 @@ pub mod pallet {
             let signature = <T as Config>::Signature::try_from(signature)
                 .map_err(|_| Error::<T>::DecodeFailure)?;
 
+            let (payload, alternative) = payloads.map_err(|_| Error::<T>::AncientBirthBlock)?;
+
             // Ensure that transaction signature is valid
-            ensure!(
-                Self::valid_signature(&call, &signer, &signature, &nonce),
-                Error::<T>::InvalidSignature
-            );
+            Self::check_signature(&signature, &signer, &payload, alternative)?;
 
-            // Increment account nonce
-            frame_system::Pallet::<T>::inc_account_nonce(signer.clone());
+            // Processing fee, withdrawn for the maximal calls weight
+            let (weight, len) = Self::weight_and_len(&calls);
+            let fee = Self::compute_fee(weight, len);
 
-            // Processing fee
+            // Ensure that sponsor pays for the calls
+            let sponsored = sponsor.is_some();
+            let payer = match sponsor {
+                Some((sponsor, sponsor_signature)) => {
+                    Self::check_sponsor(&sponsor, sponsor_signature, &signer, &payload, &calls)?;
+                    ensure!(
+                        Self::sponsorship(&sponsor).allowance >= fee,
+                        Error::<T>::SponsorAllowanceExceeded
+                    );
+                    sponsor
+                }
+                None => signer.clone(),
+            };
+
+            // Fee is withdrawn first, so the failed payment leaves no changes
             let tx_fee = T::Currency::withdraw(
-                &signer,
-                T::CallFee::get(),
+                &payer,
+                fee,
                 WithdrawReasons::FEE,
                 ExistenceRequirement::AllowDeath,
             )?;
-            T::OnChargeTransaction::on_unbalanced(tx_fee);
 
-            // Dispatch call
-            let new_origin = frame_system::RawOrigin::Signed(signer.clone()).into();
-            let res = call.dispatch_bypass_filter(new_origin).map(|_| ());
-            Self::deposit_event(Event::Executed(signer, res.map_err(|e| e.error)));
+            // Increment account nonce
+            frame_system::Pallet::<T>::inc_account_nonce(signer.clone());
+
+            // Dispatch calls
+            let actual_weight = if atomic {
+                let (actual_weight, res) = with_transaction(|| {
+                    let (actual_weight, res) = Self::dispatch_calls(&signer, calls);
+                    if res.is_ok() {
+                        TransactionOutcome::Commit((actual_weight, res))
+                    } else {
+                        TransactionOutcome::Rollback((actual_weight, res))
+                    }
+                });
+                // Events are reverted as well, failure should be reported again
+                if res.is_err() {
+                    Self::deposit_event(Event::Executed(signer.clone(), res));
+                }
+                actual_weight
+            } else {
+                Self::dispatch_calls(&signer, calls).0
+            };
+
+            // Refund fee for unused weight
+            let actual_fee = Self::compute_fee(actual_weight, len);
+            let (tx_fee, refund) = tx_fee.split(actual_fee);
+            if !refund.peek().is_zero() {
+                T::Currency::resolve_creating(&payer, refund);
+            }
+            if sponsored {
+                Sponsorships::<T>::mutate_exists(&payer, |sponsorship| {
+                    if let Some(info) = sponsorship {
+                        info.allowance = info.allowance.saturating_sub(tx_fee.peek());
+                        if info.allowance.is_zero() {
+                            *sponsorship = None;
+                        }
+                    }
+                });
+            }
+            Self::deposit_event(Event::FeePaid(signer, payer, actual_weight, tx_fee.peek()));
+            T::OnChargeTransaction::on_unbalanced(tx_fee);
 
             // Fee already charged
-            Ok(Pays::No.into())
+            Ok(PostDispatchInfo {
+                actual_weight: Some(actual_weight.saturating_add(10_000)),
+                pays_fee: Pays::No,
+            })
         }
-    }
 
-    impl<T: Config> Pallet<T> {
-        /// Verify custom signature and returns `true` if correct.
+        /// Dispatch `calls` one by one until the first failure.
+        ///
+        /// Returns actual weight of dispatched calls and the first failure.
+        fn dispatch_calls(
+            signer: &T::AccountId,
+            calls: Vec<<T as Config>::Call>,
+        ) -> (Weight, DispatchResult) {
+            let mut actual_weight: Weight = 0;
+            for call in calls {
+                let dispatch_info = call.get_dispatch_info();
+                let new_origin = frame_system::RawOrigin::Signed(signer.clone()).into();
+                let res = call.dispatch_bypass_filter(new_origin);
+                let post_info = match &res {
+                    Ok(post_info) => *post_info,
+                    Err(err) => err.post_info,
+                };
+                actual_weight =
+                    actual_weight.saturating_add(post_info.calc_actual_weight(&dispatch_info));
+
+                let res = res.map(|_| ()).map_err(|e| e.error);
+                Self::deposit_event(Event::Executed(signer.clone(), res.clone()));
+                if res.is_err() {
+                    return (actual_weight, res);
+                }
+            }
+            (actual_weight, Ok(()))
+        }
+
+        /// Fee for dispatch of the call with given weight and encoded length.
+        ///
+        /// Base extrinsic fee is included like `pallet_transaction_payment` does.
+        pub fn compute_fee(weight: Weight, len: u32) -> BalanceOf<T> {
+            let base_weight = T::BlockWeights::get()
+                .get(DispatchClass::Normal)
+                .base_extrinsic;
+            let base_fee = T::WeightToFee::calc(&base_weight);
+            let len_fee = T::TransactionByteFee::get().saturating_mul(len.into());
+            base_fee
+                .saturating_add(T::WeightToFee::calc(&weight))
+                .saturating_add(len_fee)
+        }
+
+        /// Fee withdrawn before `call` dispatch, see `compute_fee`.
+        pub fn query_call_fee(call: &<T as Config>::Call) -> BalanceOf<T> {
+            Self::compute_fee(call.get_dispatch_info().weight, call.encoded_size() as u32)
+        }
+
+        /// Fee withdrawn for the call or the batch of calls signed in the payload.
+        ///
+        /// `what` is the signed payload without `(CallMagicNumber, nonce)` prefix, i.e.
+        /// encoded call or `(calls, atomic)` batch followed by optional sponsor account and
+        /// replay protection fields. `None` is returned when it's neither of them.
+        pub fn query_payload_fee(what: &[u8]) -> Option<BalanceOf<T>> {
+            let mut input = what;
+            if let Ok(call) = <T as Config>::Call::decode(&mut input) {
+                if Self::is_payload_tail(input) {
+                    return Some(Self::query_call_fee(&call));
+                }
+            }
+
+            let mut input = what;
+            let (calls, _atomic) = <(Vec<<T as Config>::Call>, bool)>::decode(&mut input).ok()?;
+            if !Self::is_payload_tail(input) {
+                return None;
+            }
+            let (weight, len) = Self::weight_and_len(&calls);
+            Some(Self::compute_fee(weight, len))
+        }
+
+        /// Check that `input` is exactly the fields following the call in some payload version,
+        /// i.e. optional sponsor of `sponsored_call` and replay protection fields.
+        fn is_payload_tail(input: &[u8]) -> bool {
+            input.is_empty()
+                || T::AccountId::decode_all(input).is_ok()
+                || <(T::Hash, Era, T::Hash)>::decode_all(input).is_ok()
+                || <(T::AccountId, T::Hash, Era, T::Hash)>::decode_all(input).is_ok()
+        }
+
+        /// Encoded payload that should be signed for given `version`.
+        ///
+        /// Era birth block hash should be known, `AncientBirthBlock` is returned otherwise.
+        pub fn signed_payload(
+            call: &<T as Config>::Call,
+            nonce: &T::Index,
+            version: &PayloadVersion,
+        ) -> Result<Vec<u8>, InvalidTransaction> {
+            Self::versioned_payload(call, nonce, version)
+        }
+
+        /// Encoded payload of `sponsored_call` that should be signed by signer for given `version`.
+        pub fn signed_sponsored_payload(
+            call: &<T as Config>::Call,
+            sponsor: &T::AccountId,
+            nonce: &T::Index,
+            version: &PayloadVersion,
+        ) -> Result<Vec<u8>, InvalidTransaction> {
+            Self::versioned_payload(&(call, sponsor), nonce, version)
+        }
+
+        /// Encoded batch payload that should be signed for given `version`.
+        pub fn signed_batch_payload(
+            calls: &[<T as Config>::Call],
+            atomic: bool,
+            nonce: &T::Index,
+            version: &PayloadVersion,
+        ) -> Result<Vec<u8>, InvalidTransaction> {
+            Self::versioned_payload(&(calls, atomic), nonce, version)
+        }
+
+        /// Payload of given `version` and payload of alternative version if it's available.
+        fn payloads<C: Encode>(
+            what: &C,
+            nonce: &T::Index,
+            version: &PayloadVersion,
+        ) -> Result<(Vec<u8>, Option<Vec<u8>>), InvalidTransaction> {
+            let payload = Self::versioned_payload(what, nonce, version)?;
+            let alternative = match version {
+                PayloadVersion::V0 => PayloadVersion::V1(Era::Immortal),
+                PayloadVersion::V1(_) => PayloadVersion::V0,
+            };
+            Ok((payload, Self::versioned_payload(what, nonce, &alternative).ok()))
+        }
+
+        fn versioned_payload<C: Encode>(
+            what: &C,
+            nonce: &T::Index,
+            version: &PayloadVersion,
+        ) -> Result<Vec<u8>, InvalidTransaction> {
+            let mut payload = (T::CallMagicNumber::get(), *nonce, what).encode();
+            if let PayloadVersion::V1(era) = version {
+                let current = frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
+                let birth = era.birth(current).saturated_into::<T::BlockNumber>();
+                if !frame_system::BlockHash::<T>::contains_key(birth) {
+                    return Err(InvalidTransaction::AncientBirthBlock);
+                }
+
+                let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+                let era_block_hash = frame_system::Pallet::<T>::block_hash(birth);
+                (genesis_hash, era, era_block_hash).encode_to(&mut payload);
+            }
+            Ok(payload)
+        }
+
+        /// Verify custom signature of the payload and returns `true` if correct.
         pub fn valid_signature(
-            call: &Box<<T as Config>::Call>,
+            payload: &[u8],
             signer: &T::AccountId,
             signature: &T::Signature,
-            nonce: &T::Index,
         ) -> bool {
-            let payload = (T::CallMagicNumber::get(), *nonce, call.clone());
-            signature.verify(&payload.encode()[..], signer)
+            signature.check(payload, signer).is_ok()
+        }
+
+        /// Total dispatch weight and encoded length of `calls`.
+        fn weight_and_len(calls: &[<T as Config>::Call]) -> (Weight, u32) {
+            calls.iter().fold((0, 0), |(weight, len), call| {
+                (
+                    weight.saturating_add(call.get_dispatch_info().weight),
+                    len.saturating_add(call.encoded_size() as u32),
+                )
+            })
+        }
+
+        /// Transaction priority of the paid `fee`, the same as for signed extrinsics.
+        ///
+        /// Fee is scaled by the number of such transactions that could fit into the block.
+        fn fee_priority(weight: Weight, len: u32, fee: BalanceOf<T>) -> TransactionPriority {
+            let weight_saturation = T::BlockWeights::get().max_block / weight.max(1);
+            let max_block_length = *T::BlockLength::get().max.get(DispatchClass::Normal);
+            let len_saturation = max_block_length as u64 / (len as u64).max(1);
+            let coefficient: BalanceOf<T> =
+                weight_saturation.min(len_saturation).saturated_into();
+            fee.saturating_mul(coefficient).saturated_into()
+        }
+
+        /// Whether `payer` is able to pay the `fee`, nothing is withdrawn.
+        fn can_pay_fee(payer: &T::AccountId, fee: BalanceOf<T>) -> bool {
+            with_transaction(|| {
+                let withdrawn = T::Currency::withdraw(
+                    payer,
+                    fee,
+                    WithdrawReasons::FEE,
+                    ExistenceRequirement::AllowDeath,
+                );
+                TransactionOutcome::Rollback(withdrawn.is_ok())
+            })
+        }
+
+        /// Encoded payload that should be signed by sponsor of `signer` payload.
+        pub fn sponsor_payload(signer: &T::AccountId, payload: &[u8]) -> Vec<u8> {
+            (T::CallMagicNumber::get(), b"sponsor", signer, payload).encode()
+        }
+
+        /// Check that `sponsor` agrees to pay fees for `calls` of `signer`.
+        fn check_sponsor(
+            sponsor: &T::AccountId,
+            sponsor_signature: Option<Vec<u8>>,
+            signer: &T::AccountId,
+            payload: &[u8],
+            calls: &[<T as Config>::Call],
+        ) -> Result<(), Error<T>> {
+            match sponsor_signature {
+                Some(signature) => {
+                    let signature = <T as Config>::Signature::try_from(signature)
+                        .map_err(|_| Error::<T>::NotSponsored)?;
+                    let sponsor_payload = Self::sponsor_payload(signer, payload);
+                    ensure!(
+                        Self::valid_signature(&sponsor_payload, sponsor, &signature),
+                        Error::<T>::NotSponsored
+                    );
+                }
+                None => ensure!(
+                    Self::sponsorship(sponsor).open
+                        && calls.iter().all(|call| T::SponsoredCalls::contains(call)),
+                    Error::<T>::NotSponsored
+                ),
+            }
+            Ok(())
+        }
+
+        /// Check custom signature of the payload and returns the failure reason.
+        ///
+        /// On signer mismatch `alternative` payload is checked to report version mismatch.
+        fn check_signature(
+            signature: &T::Signature,
+            signer: &T::AccountId,
+            payload: &[u8],
+            alternative: Option<Vec<u8>>,
+        ) -> Result<(), Error<T>> {
+            match signature.check(payload, signer) {
+                Ok(()) => Ok(()),
+                Err(SignatureFailure::Unrecoverable) => Err(Error::<T>::UnrecoverableSignature),
+                Err(SignatureFailure::SignerMismatch) => match alternative {
+                    Some(alternative) if Self::valid_signature(&alternative, signer, signature) => {
+                        Err(Error::<T>::PayloadVersionMismatch)
+                    }
+                    _ => Err(Error::<T>::InvalidSignature),
+                },
+            }
+        }
+
+        /// Number of blocks the transaction with given payload `version` is valid for.
+        fn longevity(version: &PayloadVersion) -> TransactionLongevity {
+            match version {
+                PayloadVersion::V0 => 64,
+                PayloadVersion::V1(era) => {
+                    let current =
+                        frame_system::Pallet::<T>::block_number().saturated_into::<u64>();
+                    era.death(current).saturating_sub(current)
+                }
+            }
         }
     }
 
-    pub(crate) const SIGNATURE_DECODE_FAILURE: u8 = 1;
+    /// Custom validity code: signature decode fails, e.g. wrong signature length.
+    pub const SIGNATURE_DECODE_FAILURE: u8 = 1;
+    /// Custom validity code: signer can't be recovered from the signature.
+    pub const SIGNATURE_UNRECOVERABLE: u8 = 2;
+    /// Custom validity code: signature is made by other account.
+    pub const SIGNER_MISMATCH: u8 = 3;
+    /// Custom validity code: signature is made for payload of other version.
+    pub const PAYLOAD_VERSION_MISMATCH: u8 = 4;
+    /// Custom validity code: sponsor doesn't pay for the call.
+    pub const NOT_SPONSORED: u8 = 5;
 
     #[pallet::validate_unsigned]
     impl<T: Config> frame_support::unsigned::ValidateUnsigned for Pallet<T> {
         type Call = Call<T>;
 
         fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-            // Call decomposition (we have only one possible value here)
-            let (call, signer, signature, nonce) = match call {
+            if let Call::claim_account { to, signature } = call {
+                // The same checks and transfer as dispatch does, claim couldn't fail for free
+                let nonce = frame_system::Pallet::<T>::account_nonce(to);
+                let claim = with_transaction(|| {
+                    TransactionOutcome::Rollback(Self::do_claim(to, signature))
+                });
+                return match claim {
+                    // Claim increments nonce of `to`, the same tag as `CheckNonce` provides
+                    Ok(_) => Ok(ValidTransaction {
+                        priority: T::UnsignedPriority::get(),
+                        requires: vec![],
+                        provides: vec![(to, nonce).encode()],
+                        longevity: 64,
+                        propagate: true,
+                    }),
+                    Err(_) => InvalidTransaction::BadProof.into(),
+                };
+            }
+
+            // Call decomposition
+            let (calls, signer, signature, nonce, version, (payload, alternative)) = match call {
                 Call::call {
                     call,
                     signer,
                     signature,
                     nonce,
-                } => (call, signer, signature, nonce),
+                } => {
+                    let version = PayloadVersion::V0;
+                    let payloads = Self::payloads(&**call, nonce, &version)?;
+                    let calls = sp_std::slice::from_ref(&**call);
+                    (calls, signer, signature, nonce, version, payloads)
+                }
+                Call::versioned_call {
+                    call,
+                    signer,
+                    signature,
+                    version,
+                    nonce,
+                } => {
+                    let payloads = Self::payloads(&**call, nonce, version)?;
+                    let calls = sp_std::slice::from_ref(&**call);
+                    (calls, signer, signature, nonce, *version, payloads)
+                }
+                Call::sponsored_call {
+                    call,
+                    signer,
+                    signature,
+                    version,
+                    nonce,
+                    sponsor,
+                    ..
+                } => {
+                    let payloads = Self::payloads(&(&**call, sponsor), nonce, version)?;
+                    let calls = sp_std::slice::from_ref(&**call);
+                    (calls, signer, signature, nonce, *version, payloads)
+                }
+                Call::call_batch {
+                    calls,
+                    atomic,
+                    signer,
+                    signature,
+                    version,
+                    nonce,
+                } => {
+                    // Empty batch would only bump the nonce for the base fee
+                    if calls.is_empty() {
+                        return InvalidTransaction::Call.into();
+                    }
+                    let payloads = Self::payloads(&(&calls[..], *atomic), nonce, version)?;
+                    (&calls[..], signer, signature, nonce, *version, payloads)
+                }
                 _ => return InvalidTransaction::Call.into(),
             };
 
-            // Check that tx isn't stale
-            if *nonce != frame_system::Pallet::<T>::account_nonce(signer.clone()) {
+            // Check that tx isn't stale, future tx waits for the previous nonce
+            let account_nonce = frame_system::Pallet::<T>::account_nonce(signer.clone());
+            if *nonce < account_nonce {
                 return InvalidTransaction::Stale.into();
             }
 
             // Check signature encoding
-            if let Ok(signature) = <T as Config>::Signature::try_from(signature.clone()) {
-                // Verify signature
-                if Self::valid_signature(call, signer, &signature, nonce) {
-                    ValidTransaction::with_tag_prefix("CustomSignatures")
-                        .priority(T::UnsignedPriority::get())
-                        .and_provides((call, signer, nonce))
-                        .longevity(64_u64)
-                        .propagate(true)
-                        .build()
-                } else {
-                    // Signature mismatched to given signer
-                    InvalidTransaction::BadProof.into()
+            let signature = match <T as Config>::Signature::try_from(signature.clone()) {
+                Ok(signature) => signature,
+                Err(_) => return InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into(),
+            };
+
+            // Verify signature
+            let mut res = Self::check_signature(&signature, signer, &payload, alternative);
+
+            let (weight, len) = Self::weight_and_len(calls);
+            let fee = Self::compute_fee(weight, len);
+
+            // Verify sponsorship
+            if let Call::sponsored_call {
+                sponsor,
+                sponsor_signature,
+                ..
+            } = call
+            {
+                res = res
+                    .and_then(|_| {
+                        Self::check_sponsor(
+                            sponsor,
+                            sponsor_signature.clone(),
+                            signer,
+                            &payload,
+                            calls,
+                        )
+                    })
+                    .and_then(|_| {
+                        if Self::sponsorship(sponsor).allowance >= fee {
+                            Ok(())
+                        } else {
+                            Err(Error::<T>::SponsorAllowanceExceeded)
+                        }
+                    });
+            }
+
+            // Ensure that payer is able to pay the fee, like `ChargeTransactionPayment` does
+            let payer = match call {
+                Call::sponsored_call { sponsor, .. } => sponsor,
+                _ => signer,
+            };
+            if res.is_ok() && !Self::can_pay_fee(payer, fee) {
+                return InvalidTransaction::Payment.into();
+            }
+
+            match res {
+                // The same tags as `CheckNonce` provides, so signed and unsigned transactions
+                // of the account are ordered and replaced by each other in the pool
+                Ok(()) => {
+                    let requires = if *nonce > account_nonce {
+                        vec![(signer, *nonce - One::one()).encode()]
+                    } else {
+                        vec![]
+                    };
+                    Ok(ValidTransaction {
+                        priority: T::UnsignedPriority::get()
+                            .saturating_add(Self::fee_priority(weight, len, fee)),
+                        requires,
+                        provides: vec![(signer, nonce).encode()],
+                        longevity: Self::longevity(&version),
+                        propagate: true,
+                    })
                 }
-            } else {
-                // Signature encoding broken
-                InvalidTransaction::Custom(SIGNATURE_DECODE_FAILURE).into()
+                Err(Error::<T>::UnrecoverableSignature) => {
+                    InvalidTransaction::Custom(SIGNATURE_UNRECOVERABLE).into()
+                }
+                Err(Error::<T>::PayloadVersionMismatch) => {
+                    InvalidTransaction::Custom(PAYLOAD_VERSION_MISMATCH).into()
+                }
+                Err(Error::<T>::NotSponsored) | Err(Error::<T>::SponsorAllowanceExceeded) => {
+                    InvalidTransaction::Custom(NOT_SPONSORED).into()
+                }
+                Err(_) => InvalidTransaction::Custom(SIGNER_MISMATCH).into(),
             }
         }
     }
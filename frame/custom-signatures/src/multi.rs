@@ -0,0 +1,191 @@
+//! Signatures of several schemes in one pallet instance.
+//!
+//! `AnySignature` is SCALE-encoded enum, so the leading tag byte of the signature selects
+//! the scheme:
+//! - `0x00`: Ethereum `personal_sign` of the payload, see `EthereumSignature`;
+//! - `0x01`: EIP-712 typed data of the payload, see `Eip712Signature`;
+//! - `0x02`: raw secp256k1 signature of the payload keccak-256 hash;
+//! - `0x03`: ed25519 signature of the payload, e.g. made by hardware wallet.
+//!
+//! Untagged 65-byte Ethereum signature of legacy clients is accepted as `Eip712` one, that
+//! checks `personal_sign` message as well.
+//!
+//! Signer account of secp256k1 schemes is derived from compressed public key as `MultiSigner`
+//! does, signer account of ed25519 scheme is the public key itself.
+
+use crate::{
+    eip712::{Eip712Domain, Eip712Signature},
+    ethereum::EthereumSignature,
+    evm::{public_to_address, RecoverAddress},
+    CheckSignature, SignatureFailure,
+};
+use codec::{Decode, Encode};
+use sp_core::{ecdsa, ed25519, H160};
+use sp_io::{
+    crypto::{ed25519_verify, secp256k1_ecdsa_recover, secp256k1_ecdsa_recover_compressed},
+    hashing::keccak_256,
+};
+use sp_runtime::traits::{IdentifyAccount, Lazy, Verify};
+use sp_runtime::{AccountId32, MultiSigner};
+use sp_std::{convert::TryFrom, prelude::*};
+
+/// Signature of any supported scheme, the first encoded byte is the scheme tag.
+#[derive(Encode, Decode, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(D))]
+pub enum AnySignature<D> {
+    /// Ethereum `personal_sign` signature.
+    #[codec(index = 0)]
+    Ethereum(EthereumSignature),
+    /// EIP-712 typed data signature.
+    #[codec(index = 1)]
+    Eip712(Eip712Signature<D>),
+    /// Raw secp256k1 signature of keccak-256 hash.
+    #[codec(index = 2)]
+    Secp256k1([u8; 65]),
+    /// Ed25519 signature.
+    #[codec(index = 3)]
+    Ed25519(ed25519::Signature),
+}
+
+impl<D> Clone for AnySignature<D> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Ethereum(signature) => Self::Ethereum(signature.clone()),
+            Self::Eip712(signature) => Self::Eip712(signature.clone()),
+            Self::Secp256k1(signature) => Self::Secp256k1(*signature),
+            Self::Ed25519(signature) => Self::Ed25519(signature.clone()),
+        }
+    }
+}
+
+impl<D> PartialEq for AnySignature<D> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Ethereum(a), Self::Ethereum(b)) => a == b,
+            (Self::Eip712(a), Self::Eip712(b)) => a == b,
+            (Self::Secp256k1(a), Self::Secp256k1(b)) => a[..] == b[..],
+            (Self::Ed25519(a), Self::Ed25519(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<D> Eq for AnySignature<D> {}
+
+impl<D> sp_std::fmt::Debug for AnySignature<D> {
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+        match self {
+            Self::Ethereum(signature) => write!(f, "AnySignature::{:?}", signature),
+            Self::Eip712(signature) => write!(f, "AnySignature::{:?}", signature),
+            Self::Secp256k1(signature) => {
+                write!(f, "AnySignature::Secp256k1({:?})", &signature[..])
+            }
+            Self::Ed25519(signature) => write!(f, "AnySignature::{:?}", signature),
+        }
+    }
+}
+
+/// Tagged signature, trailing bytes are not allowed.
+///
+/// Legacy untagged signature is 65 bytes long with Ethereum `v` in the last byte, either of
+/// 27/28 or of 0/1 notation; the latter is normalised to the former.
+/// The only tagged signature of the same length is ed25519 one: its `S` scalar is less than
+/// 2^253, so it never ends with 27 or 28, but it may end with 0 or 1. Such 65 bytes starting
+/// with the ed25519 tag are decoded as tagged ed25519 signature, so legacy clients should
+/// prefer 27/28 notation.
+impl<D> TryFrom<Vec<u8>> for AnySignature<D> {
+    type Error = ();
+
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        if data.len() == 65 {
+            let v = match data[64] {
+                27 | 28 => Some(data[64]),
+                0 | 1 if data[0] != 3 => Some(data[64] + 27),
+                _ => None,
+            };
+            if let Some(v) = v {
+                let mut signature = [0u8; 65];
+                signature.copy_from_slice(&data[..]);
+                signature[64] = v;
+                return Ok(Self::Eip712(Eip712Signature::new(signature)));
+            }
+        }
+
+        let mut input = &data[..];
+        let signature = Self::decode(&mut input).map_err(|_| ())?;
+        if input.is_empty() {
+            Ok(signature)
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Recover compressed public key of raw secp256k1 signature of the message hash.
+fn secp256k1_recover_compressed(signature: &[u8; 65], msg: &[u8]) -> Option<[u8; 33]> {
+    secp256k1_ecdsa_recover_compressed(signature, &keccak_256(msg)).ok()
+}
+
+impl<D: Eip712Domain> CheckSignature<AccountId32> for AnySignature<D> {
+    fn check(&self, msg: &[u8], signer: &AccountId32) -> Result<(), SignatureFailure> {
+        match self {
+            Self::Ethereum(signature) => signature.check(msg, signer),
+            Self::Eip712(signature) => signature.check(msg, signer),
+            Self::Secp256k1(signature) => match secp256k1_recover_compressed(signature, msg) {
+                Some(public) => {
+                    let account =
+                        MultiSigner::from(ecdsa::Public::from_raw(public)).into_account();
+                    if account == *signer {
+                        Ok(())
+                    } else {
+                        Err(SignatureFailure::SignerMismatch)
+                    }
+                }
+                None => Err(SignatureFailure::Unrecoverable),
+            },
+            Self::Ed25519(signature) => {
+                let public = ed25519::Public::from_raw(signer.clone().into());
+                if ed25519_verify(signature, msg, &public) {
+                    Ok(())
+                } else {
+                    Err(SignatureFailure::SignerMismatch)
+                }
+            }
+        }
+    }
+}
+
+impl<D: Eip712Domain> Verify for AnySignature<D> {
+    type Signer = MultiSigner;
+
+    fn verify<L: Lazy<[u8]>>(&self, mut msg: L, account: &AccountId32) -> bool {
+        self.check(msg.get(), account).is_ok()
+    }
+}
+
+/// Ed25519 signer has no Ethereum address, its signature is checked natively.
+impl<D: Eip712Domain> RecoverAddress for AnySignature<D> {
+    fn recover_addresses(&self, msg: &[u8]) -> Vec<H160> {
+        match self {
+            Self::Ethereum(signature) => signature.recover_addresses(msg),
+            Self::Eip712(signature) => signature.recover_addresses(msg),
+            Self::Secp256k1(signature) => secp256k1_ecdsa_recover(signature, &keccak_256(msg))
+                .ok()
+                .map(|public| public_to_address(&public))
+                .into_iter()
+                .collect(),
+            Self::Ed25519(_) => Vec::new(),
+        }
+    }
+
+    fn check_native(
+        &self,
+        msg: &[u8],
+        signer: &AccountId32,
+    ) -> Option<Result<(), SignatureFailure>> {
+        match self {
+            Self::Ed25519(_) => Some(self.check(msg, signer)),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Off-chain helpers to build and sign pallet payloads by secp256k1 key.
+//!
+//! ```nocompile
+//! let pair = ecdsa::Pair::from_string("//Alice", None)?;
+//! let payload = signer::payload(CallMagicNumber::get(), nonce, &call, None);
+//! let signature = signer::eth_sign(&pair, &payload);
+//! ```
+
+use crate::{
+    eip712::{self, Eip712Domain},
+    ethereum,
+    evm::public_to_address,
+};
+use codec::Encode;
+use sp_core::{ecdsa, Pair, H160, H256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::{generic::Era, traits::IdentifyAccount, AccountId32, MultiSigner};
+
+/// Replay protection fields of `PayloadVersion::V1` payload.
+#[derive(Clone, Copy, Debug)]
+pub struct Mortality {
+    /// Transaction era.
+    pub era: Era,
+    /// Hash of the chain genesis block.
+    pub genesis_hash: H256,
+    /// Hash of the era birth block.
+    pub era_block_hash: H256,
+}
+
+/// Payload of `call` and `versioned_call`.
+///
+/// Payload is `PayloadVersion::V0` when `mortality` isn't set and `PayloadVersion::V1` otherwise.
+pub fn payload<C: Encode>(
+    magic: u16,
+    nonce: u32,
+    call: &C,
+    mortality: Option<&Mortality>,
+) -> Vec<u8> {
+    let mut payload = (magic, nonce, call).encode();
+    if let Some(mortality) = mortality {
+        (mortality.genesis_hash, mortality.era, mortality.era_block_hash).encode_to(&mut payload);
+    }
+    payload
+}
+
+/// Payload of `sponsored_call` signed by the signer.
+pub fn sponsored_payload<C: Encode>(
+    magic: u16,
+    nonce: u32,
+    call: &C,
+    sponsor: &AccountId32,
+    mortality: Option<&Mortality>,
+) -> Vec<u8> {
+    payload(magic, nonce, &(call, sponsor), mortality)
+}
+
+/// Payload of `call_batch`.
+pub fn batch_payload<C: Encode>(
+    magic: u16,
+    nonce: u32,
+    calls: &[C],
+    atomic: bool,
+    mortality: Option<&Mortality>,
+) -> Vec<u8> {
+    payload(magic, nonce, &(calls, atomic), mortality)
+}
+
+/// Sign the same message as Ethereum RPC's `personal_sign` and `eth_sign` do.
+pub fn eth_sign(pair: &ecdsa::Pair, payload: &[u8]) -> [u8; 65] {
+    let hash = keccak_256(&ethereum::signable_message(payload));
+    to_ethereum(pair.sign_prehashed(&hash))
+}
+
+/// Sign the same message as Ethereum RPC's `eth_signTypedData_v4` does.
+///
+/// Returns `None` for malformed payload.
+pub fn eip712_sign<D: Eip712Domain>(pair: &ecdsa::Pair, payload: &[u8]) -> Option<[u8; 65]> {
+    eip712::signable_message::<D>(payload).map(|hash| to_ethereum(pair.sign_prehashed(&hash)))
+}
+
+/// Sign keccak-256 hash of the payload, see `AnySignature::Secp256k1`.
+pub fn raw_sign(pair: &ecdsa::Pair, payload: &[u8]) -> [u8; 65] {
+    to_ethereum(pair.sign_prehashed(&keccak_256(payload)))
+}
+
+/// Ethereum uses 27/28 notation of recovery ID.
+fn to_ethereum(signature: ecdsa::Signature) -> [u8; 65] {
+    let mut signature: [u8; 65] = signature.into();
+    signature[64] += 27;
+    signature
+}
+
+/// Signer account derived from compressed public key, as `MultiSigner` does.
+pub fn account(pair: &ecdsa::Pair) -> AccountId32 {
+    MultiSigner::from(pair.public()).into_account()
+}
+
+/// Ethereum address of the signer, see `evm::AddressMapping` for the account.
+pub fn address(pair: &ecdsa::Pair) -> H160 {
+    let public = libsecp256k1::PublicKey::parse_compressed(&pair.public().0)
+        .expect("pair public key is valid; qed");
+    let mut uncompressed = [0u8; 64];
+    uncompressed.copy_from_slice(&public.serialize()[1..]);
+    public_to_address(&uncompressed)
+}
@@ -1,22 +1,28 @@
 use crate as custom_signatures;
 use codec::Encode;
 use custom_signatures::*;
-use frame_support::{assert_err, assert_ok, parameter_types};
+use frame_support::{
+    assert_err, assert_noop, assert_ok, parameter_types,
+    traits::Currency,
+    weights::{DispatchClass, GetDispatchInfo, IdentityFee, WeightToFeePolynomial},
+};
 use hex_literal::hex;
 use sp_core::{ecdsa, Pair};
 use sp_io::hashing::keccak_256;
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::{
+    generic::Era,
     testing::{Header, H256},
     traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify},
     transaction_validity::TransactionPriority,
-    MultiSignature, MultiSigner,
+    DispatchResult, MultiSignature, MultiSigner,
 };
 
 pub const ECDSA_SEED: [u8; 32] =
     hex_literal::hex!["7e9c7ad85df5cdc88659f53e06fb2eb9bab3ebc59083a3190eaf2c730332529c"];
 
 type Balance = u128;
+const INITIAL_BALANCE: Balance = 1_000_000_000_000;
 type BlockNumber = u64;
 type Signature = MultiSignature;
 type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
@@ -31,7 +37,7 @@ frame_support::construct_runtime!(
     {
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-        CustomSignatures: custom_signatures::{Pallet, Call, Event<T>},
+        CustomSignatures: custom_signatures::{Pallet, Call, Storage, Event<T>},
     }
 );
 
@@ -82,20 +88,57 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-    pub const Priority: TransactionPriority = TransactionPriority::max_value();
-    pub const CallFee: Balance = 42;
+    pub const Priority: TransactionPriority = TransactionPriority::max_value() / 2;
+    pub const TransactionByteFee: Balance = 10;
     pub const CallMagicNumber: u16 = 0xff50;
 }
 
+pub struct TestDomain;
+impl eip712::Eip712Domain for TestDomain {
+    fn name() -> Vec<u8> {
+        b"Astar".to_vec()
+    }
+    fn chain_id() -> u64 {
+        0x50
+    }
+    fn pallet_index() -> u8 {
+        2
+    }
+    fn call_fee(call: &[u8]) -> u128 {
+        CustomSignatures::query_payload_fee(call).unwrap_or_default()
+    }
+}
+
+/// The same mapping as runtime EVM `AddressMapping`.
+pub struct EvmAddressMapping;
+impl evm::AddressMapping<AccountId> for EvmAddressMapping {
+    fn into_account_id(address: sp_core::H160) -> AccountId {
+        <pallet_evm::HashedAddressMapping<BlakeTwo256> as pallet_evm::AddressMapping<
+            AccountId,
+        >>::into_account_id(address)
+    }
+}
+
+/// Only balance transfers are paid by open sponsors.
+pub struct SponsoredCalls;
+impl frame_support::traits::Contains<Call> for SponsoredCalls {
+    fn contains(call: &Call) -> bool {
+        matches!(call, Call::Balances(_))
+    }
+}
+
 impl Config for Runtime {
     type Event = Event;
     type Call = Call;
-    type Signature = ethereum::EthereumSignature;
+    type Signature = eip712::Eip712Signature<TestDomain>;
     type Signer = <Signature as Verify>::Signer;
     type CallMagicNumber = CallMagicNumber;
     type Currency = Balances;
-    type CallFee = CallFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type TransactionByteFee = TransactionByteFee;
     type OnChargeTransaction = ();
+    type ClaimAccount = evm::LegacyToEvmAccount<EvmAddressMapping>;
+    type SponsoredCalls = SponsoredCalls;
     type UnsignedPriority = Priority;
 }
 
@@ -107,7 +150,7 @@ fn new_test_ext() -> sp_io::TestExternalities {
     let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
     let account = MultiSigner::from(pair.public()).into_account();
     let _ = pallet_balances::GenesisConfig::<Runtime> {
-        balances: vec![(account, 1_000_000_000)],
+        balances: vec![(account, INITIAL_BALANCE)],
     }
     .assimilate_storage(&mut storage);
     storage.into()
@@ -126,6 +169,18 @@ fn eth_sign(seed: &[u8; 32], data: &[u8]) -> Vec<u8> {
     out
 }
 
+/// Simple `eth_signTypedData_v4` implementation for the pallet payload
+fn eip712_sign(seed: &[u8; 32], payload: &[u8]) -> Vec<u8> {
+    let hash = eip712::signable_message::<TestDomain>(payload).expect("valid payload");
+    let ecdsa_msg = libsecp256k1::Message::parse(&hash);
+    let secret = libsecp256k1::SecretKey::parse(&seed).expect("valid seed");
+    let (signature, recovery_id) = libsecp256k1::sign(&ecdsa_msg, &secret);
+    let mut out = Vec::new();
+    out.extend_from_slice(&signature.serialize()[..]);
+    out.push(recovery_id.serialize() + 27);
+    out
+}
+
 #[test]
 fn eth_sign_works() {
     let seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
@@ -168,6 +223,7 @@ fn balance_transfer() {
         .into();
         let payload = (0xff50u16, 0u32, call.clone());
         let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref()).into();
+        let fee = CustomSignatures::query_call_fee(&call);
 
         assert_eq!(System::account(account.clone()).nonce, 0);
         assert_ok!(CustomSignatures::call(
@@ -179,7 +235,10 @@ fn balance_transfer() {
         ));
         assert_eq!(System::account(alice.clone()).data.free, 1_000);
         assert_eq!(System::account(account.clone()).nonce, 1);
-        assert_eq!(System::account(account.clone()).data.free, 999_998_958);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 1_000 - fee
+        );
 
         let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref()).into();
         assert_err!(
@@ -205,7 +264,10 @@ fn balance_transfer() {
         ));
         assert_eq!(System::account(alice).data.free, 2_000);
         assert_eq!(System::account(account.clone()).nonce, 2);
-        assert_eq!(System::account(account.clone()).data.free, 999_997_916);
+        assert_eq!(
+            System::account(account.clone()).data.free,
+            INITIAL_BALANCE - 2_000 - 2 * fee
+        );
     })
 }
 
@@ -241,3 +303,987 @@ fn call_fixtures() {
     let signature = hex!["6ecb474240df46ee5cde8f51cf5ccf4c75d15ac3c1772aea6c8189604263c98b16350883438c4eaa447ebcb6889d516f70351fd704bb3521072cd2fccc7c99dc1c"];
     assert_eq!(eth_sign(&seed, payload.encode().as_ref()), signature)
 }
+
+#[test]
+fn eip712_balance_transfer() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let payload = (0xff50u16, 0u32, call.clone());
+        let signature = eip712_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        // Typed data is bound to the call fields
+        let other_payload = (0xff50u16, 1u32, call.clone());
+        let other_signature = eip712_sign(&ECDSA_SEED, other_payload.encode().as_ref());
+        assert_err!(
+            CustomSignatures::call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                other_signature,
+                0,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+
+        assert_ok!(CustomSignatures::call(
+            Origin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            0,
+        ));
+        assert_eq!(System::account(alice).data.free, 1_000);
+        assert_eq!(System::account(account.clone()).nonce, 1);
+        assert_eq!(
+            System::account(account).data.free,
+            INITIAL_BALANCE - 1_000 - CustomSignatures::query_call_fee(&call)
+        );
+    })
+}
+
+#[test]
+fn eip712_message_is_ok() {
+    // Too short payload
+    assert!(eip712::signable_message::<TestDomain>(&hex!["50ff00000000"]).is_none());
+
+    let payload = hex!["50ff000000000000c4305fb88b6ccb43d6552dc11d18e7b0ee3185247adcc6e885eb284adf6c563da10f"];
+    let message = eip712::signable_message::<TestDomain>(&payload[..]).unwrap();
+
+    // Magic number isn't a part of typed data
+    let mut other_magic = payload;
+    other_magic[0] = 0x51;
+    assert_eq!(
+        eip712::signable_message::<TestDomain>(&other_magic[..]),
+        Some(message),
+    );
+
+    // Call arguments are
+    let mut other_args = payload;
+    other_args[10] = 0;
+    assert_ne!(
+        eip712::signable_message::<TestDomain>(&other_args[..]),
+        Some(message),
+    );
+}
+
+#[test]
+fn versioned_call_replay_protection() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let genesis_hash = System::block_hash(0);
+
+        // Immortal payload is bound to the genesis hash
+        let payload = (
+            0xff50u16,
+            0u32,
+            call.clone(),
+            H256::repeat_byte(1),
+            Era::Immortal,
+            genesis_hash,
+        );
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        assert_err!(
+            CustomSignatures::versioned_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature,
+                PayloadVersion::V1(Era::Immortal),
+                0,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+
+        let payload = (
+            0xff50u16,
+            0u32,
+            call.clone(),
+            genesis_hash,
+            Era::Immortal,
+            genesis_hash,
+        );
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        // V0 payload doesn't match
+        assert_err!(
+            CustomSignatures::call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                0,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+        assert_ok!(CustomSignatures::versioned_call(
+            Origin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            PayloadVersion::V1(Era::Immortal),
+            0,
+        ));
+        assert_eq!(System::account(alice.clone()).data.free, 1_000);
+
+        // Mortal payload is bound to the era birth block hash
+        let birth_hash = H256::repeat_byte(2);
+        frame_system::BlockHash::<Runtime>::insert(10, birth_hash);
+        System::set_block_number(10);
+        let era = Era::mortal(16, 10);
+        let payload = (0xff50u16, 1u32, call.clone(), genesis_hash, era, birth_hash);
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        // Era is over
+        System::set_block_number(30);
+        assert_err!(
+            CustomSignatures::versioned_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                PayloadVersion::V1(era),
+                1,
+            ),
+            Error::<Runtime>::AncientBirthBlock,
+        );
+
+        System::set_block_number(20);
+        assert_ok!(CustomSignatures::versioned_call(
+            Origin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            PayloadVersion::V1(era),
+            1,
+        ));
+        assert_eq!(System::account(alice).data.free, 2_000);
+        assert_eq!(System::account(account).nonce, 2);
+    })
+}
+
+#[test]
+fn call_fee_is_weight_based() {
+    new_test_ext().execute_with(|| {
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+        let transfer: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice,
+            value: 1_000,
+        }
+        .into();
+        let remark: Call = frame_system::Call::<Runtime>::remark { remark: vec![] }.into();
+
+        let base_weight = <Runtime as frame_system::Config>::BlockWeights::get()
+            .get(DispatchClass::Normal)
+            .base_extrinsic;
+        assert_eq!(
+            CustomSignatures::query_call_fee(&transfer),
+            IdentityFee::<Balance>::calc(&base_weight)
+                + IdentityFee::<Balance>::calc(&transfer.get_dispatch_info().weight)
+                + 10 * transfer.encode().len() as Balance,
+        );
+        assert!(
+            CustomSignatures::query_call_fee(&remark) > IdentityFee::<Balance>::calc(&base_weight)
+        );
+        assert!(
+            CustomSignatures::query_call_fee(&transfer) > CustomSignatures::query_call_fee(&remark)
+        );
+
+        // Fee of signed payload fields follows the payload variant
+        let batch_fee = IdentityFee::<Balance>::calc(&base_weight)
+            + IdentityFee::<Balance>::calc(
+                &(transfer.get_dispatch_info().weight + remark.get_dispatch_info().weight),
+            )
+            + 10 * (transfer.encode().len() + remark.encode().len()) as Balance;
+        assert_eq!(
+            CustomSignatures::query_payload_fee(&transfer.encode()),
+            Some(CustomSignatures::query_call_fee(&transfer)),
+        );
+        let batch = vec![transfer.clone(), remark.clone()];
+        assert_eq!(
+            CustomSignatures::query_payload_fee(&(batch.clone(), true).encode()),
+            Some(batch_fee),
+        );
+        let mut v1_batch = (batch, false).encode();
+        (H256::zero(), Era::Immortal, H256::zero()).encode_to(&mut v1_batch);
+        assert_eq!(CustomSignatures::query_payload_fee(&v1_batch), Some(batch_fee));
+        assert_eq!(CustomSignatures::query_payload_fee(&[0xff; 8]), None);
+
+        // Fee is charged for failed call too
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: Keyring::Bob.into(),
+            value: 2 * INITIAL_BALANCE,
+        }
+        .into();
+        let payload = (0xff50u16, 0u32, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        assert_ok!(CustomSignatures::call(
+            Origin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            0,
+        ));
+        assert_eq!(
+            System::account(account).data.free,
+            INITIAL_BALANCE - CustomSignatures::query_call_fee(&call)
+        );
+    })
+}
+
+fn executed_events() -> Vec<DispatchResult> {
+    System::events()
+        .into_iter()
+        .filter_map(|r| match r.event {
+            Event::CustomSignatures(custom_signatures::Event::Executed(_, res)) => Some(res),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn call_batch() {
+    use frame_support::unsigned::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+
+        let transfer: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let failed: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 2 * INITIAL_BALANCE,
+        }
+        .into();
+        let calls = vec![transfer.clone(), failed, transfer];
+
+        // Batch stops at the first failure
+        let payload = (0xff50u16, 0u32, calls.clone(), false);
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        // Atomic flag is signed
+        assert_err!(
+            CustomSignatures::call_batch(
+                Origin::none(),
+                calls.clone(),
+                true,
+                account.clone(),
+                signature.clone(),
+                PayloadVersion::V0,
+                0,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+        assert_ok!(CustomSignatures::call_batch(
+            Origin::none(),
+            calls.clone(),
+            false,
+            account.clone(),
+            signature,
+            PayloadVersion::V0,
+            0,
+        ));
+        assert_eq!(System::account(alice.clone()).data.free, 1_000);
+        assert_eq!(System::account(account.clone()).nonce, 1);
+        assert_eq!(executed_events().len(), 2);
+        assert_ok!(executed_events()[0]);
+        assert!(executed_events()[1].is_err());
+
+        // Atomic batch reverts all calls
+        System::reset_events();
+        let payload = (0xff50u16, 1u32, calls.clone(), true);
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        assert_ok!(CustomSignatures::call_batch(
+            Origin::none(),
+            calls,
+            true,
+            account.clone(),
+            signature,
+            PayloadVersion::V0,
+            1,
+        ));
+        assert_eq!(System::account(alice).data.free, 1_000);
+        assert_eq!(System::account(account.clone()).nonce, 2);
+        assert_eq!(executed_events().len(), 1);
+        assert!(executed_events()[0].is_err());
+
+        // Empty batch isn't accepted
+        let payload = (0xff50u16, 2u32, Vec::<Call>::new(), false);
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        let empty_batch = custom_signatures::Call::<Runtime>::call_batch {
+            calls: vec![],
+            atomic: false,
+            signer: account.clone(),
+            signature: signature.clone(),
+            version: PayloadVersion::V0,
+            nonce: 2,
+        };
+        assert_eq!(
+            CustomSignatures::validate_unsigned(TransactionSource::External, &empty_batch),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Call)),
+        );
+        assert_err!(
+            CustomSignatures::call_batch(
+                Origin::none(),
+                vec![],
+                false,
+                account.clone(),
+                signature,
+                PayloadVersion::V0,
+                2,
+            ),
+            Error::<Runtime>::EmptyBatch,
+        );
+        assert_eq!(System::account(account).nonce, 2);
+    })
+}
+
+#[test]
+fn signature_failure_reasons() {
+    use frame_support::unsigned::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+    };
+
+    fn invalid(code: u8) -> TransactionValidity {
+        Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(code)))
+    }
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let bob: <Runtime as frame_system::Config>::AccountId = Keyring::Bob.into();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: bob.clone(),
+            value: 1_000,
+        }
+        .into();
+        let payload = (0xff50u16, 0u32, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+
+        let validate = |signer: &AccountId, signature: Vec<u8>, version| {
+            let call = custom_signatures::Call::<Runtime>::versioned_call {
+                call: Box::new(call.clone()),
+                signer: signer.clone(),
+                signature,
+                version,
+                nonce: 0,
+            };
+            CustomSignatures::validate_unsigned(TransactionSource::External, &call)
+        };
+
+        assert_ok!(validate(&account, signature.clone(), PayloadVersion::V0));
+        assert_eq!(
+            validate(&account, signature[1..].to_vec(), PayloadVersion::V0),
+            invalid(SIGNATURE_DECODE_FAILURE),
+        );
+        assert_eq!(
+            validate(&account, vec![0u8; 65], PayloadVersion::V0),
+            invalid(SIGNATURE_UNRECOVERABLE),
+        );
+        assert_eq!(
+            validate(&bob, signature.clone(), PayloadVersion::V0),
+            invalid(SIGNER_MISMATCH),
+        );
+        assert_eq!(
+            validate(&account, signature.clone(), PayloadVersion::V1(Era::Immortal)),
+            invalid(PAYLOAD_VERSION_MISMATCH),
+        );
+
+        // The same reasons are reported by dispatch
+        assert_err!(
+            CustomSignatures::versioned_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                vec![0u8; 65],
+                PayloadVersion::V0,
+                0,
+            ),
+            Error::<Runtime>::UnrecoverableSignature,
+        );
+        assert_err!(
+            CustomSignatures::versioned_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature,
+                PayloadVersion::V1(Era::Immortal),
+                0,
+            ),
+            Error::<Runtime>::PayloadVersionMismatch,
+        );
+    })
+}
+
+#[test]
+fn fee_paid_event() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: Keyring::Alice.into(),
+            value: 1_000,
+        }
+        .into();
+        let payload = (0xff50u16, 0u32, call.clone());
+        let signature = eth_sign(&ECDSA_SEED, payload.encode().as_ref());
+        assert_ok!(CustomSignatures::call(
+            Origin::none(),
+            Box::new(call.clone()),
+            account.clone(),
+            signature,
+            0,
+        ));
+
+        let weight = call.get_dispatch_info().weight;
+        assert_eq!(
+            System::events().last().map(|r| r.event.clone()),
+            Some(Event::CustomSignatures(custom_signatures::Event::FeePaid(
+                account.clone(),
+                account,
+                weight,
+                CustomSignatures::query_call_fee(&call),
+            ))),
+        );
+    })
+}
+
+#[test]
+fn evm_signature_account() {
+    use sp_core::H160;
+    use sp_runtime::traits::Hash;
+    use sp_std::convert::TryFrom;
+
+    type EvmSignature = evm::EvmSignature<ethereum::EthereumSignature, EvmAddressMapping>;
+
+    let seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+    let address = H160::from(hex!["f39fd6e51aad88f6f4ce6ab8827279cfffb92266"]);
+    let account = <EvmSignature as Verify>::Signer::from(address).into_account();
+    // The same as `HashedAddressMapping<BlakeTwo256>` of EVM
+    let mut data = b"evm:".to_vec();
+    data.extend_from_slice(&address[..]);
+    assert_eq!(account, AccountId::from(<[u8; 32]>::from(BlakeTwo256::hash(&data))));
+
+    let msg = b"test evm signed message";
+    let signature = EvmSignature::try_from(eth_sign(&seed, msg)).unwrap();
+    assert!(signature.verify(&msg[..], &account));
+
+    // Account of compressed public key isn't the signer
+    let pair = ecdsa::Pair::from_seed(&seed);
+    let legacy = MultiSigner::from(pair.public()).into_account();
+    assert!(!signature.verify(&msg[..], &legacy));
+}
+
+#[test]
+fn claim_account() {
+    use frame_support::{
+        traits::{LockableCurrency, WithdrawReasons},
+        unsigned::ValidateUnsigned,
+    };
+    use sp_core::H160;
+    use sp_runtime::traits::Hash;
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError,
+    };
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let legacy = MultiSigner::from(pair.public()).into_account();
+        let public = libsecp256k1::PublicKey::from_secret_key(
+            &libsecp256k1::SecretKey::parse(&ECDSA_SEED).unwrap(),
+        );
+        let address = H160::from_slice(&keccak_256(&public.serialize()[1..])[12..]);
+        let mut data = b"evm:".to_vec();
+        data.extend_from_slice(&address[..]);
+        let evm_account = AccountId::from(<[u8; 32]>::from(BlakeTwo256::hash(&data)));
+
+        let claim = |to: &AccountId, signature: &Vec<u8>| {
+            CustomSignatures::claim_account(Origin::none(), to.clone(), signature.clone())
+        };
+        let validate = |to: &AccountId, signature: &Vec<u8>| {
+            let call = custom_signatures::Call::<Runtime>::claim_account {
+                to: to.clone(),
+                signature: signature.clone(),
+            };
+            CustomSignatures::validate_unsigned(TransactionSource::External, &call)
+        };
+
+        // Claim is bound to the chain
+        let signature = eth_sign(&ECDSA_SEED, &(0xff50u16, b"claim").encode());
+        assert_err!(claim(&evm_account, &signature), Error::<Runtime>::InvalidClaim);
+
+        // Claim is bound to the destination
+        let alice: AccountId = Keyring::Alice.into();
+        let signature = eth_sign(&ECDSA_SEED, &CustomSignatures::claim_payload(&alice, &0));
+        assert!(validate(&alice, &signature).is_err());
+        assert_err!(claim(&alice, &signature), Error::<Runtime>::InvalidClaim);
+
+        // Claim of fully locked account isn't valid
+        let signature = eth_sign(&ECDSA_SEED, &CustomSignatures::claim_payload(&evm_account, &0));
+        Balances::set_lock(*b"testlock", &legacy, INITIAL_BALANCE, WithdrawReasons::all());
+        assert_eq!(
+            validate(&evm_account, &signature),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::BadProof)),
+        );
+        assert_err!(claim(&evm_account, &signature), Error::<Runtime>::NothingToClaim);
+        assert_eq!(System::account(legacy.clone()).data.free, INITIAL_BALANCE);
+
+        // Locked funds are left on the claimed account
+        const LOCKED: Balance = 1_000;
+        Balances::set_lock(*b"testlock", &legacy, LOCKED, WithdrawReasons::all());
+        assert!(validate(&evm_account, &signature).is_ok());
+        assert_ok!(claim(&evm_account, &signature));
+        assert_eq!(System::account(legacy.clone()).data.free, LOCKED);
+        assert_eq!(System::account(evm_account.clone()).data.free, INITIAL_BALANCE - LOCKED);
+        assert_eq!(System::account(evm_account.clone()).nonce, 1);
+        assert_eq!(
+            System::events().last().map(|r| r.event.clone()),
+            Some(Event::CustomSignatures(
+                custom_signatures::Event::AccountClaimed(
+                    legacy.clone(),
+                    evm_account.clone(),
+                    INITIAL_BALANCE - LOCKED
+                )
+            )),
+        );
+
+        // Nothing left to claim
+        let signature = eth_sign(&ECDSA_SEED, &CustomSignatures::claim_payload(&evm_account, &1));
+        assert_err!(claim(&evm_account, &signature), Error::<Runtime>::NothingToClaim);
+
+        // Claim can't be replayed when legacy account is funded again
+        let _ = Balances::deposit_creating(&legacy, INITIAL_BALANCE);
+        let replayed = eth_sign(&ECDSA_SEED, &CustomSignatures::claim_payload(&evm_account, &0));
+        assert_err!(claim(&evm_account, &replayed), Error::<Runtime>::InvalidClaim);
+        assert_ok!(claim(&evm_account, &signature));
+        assert_eq!(System::account(evm_account).data.free, 2 * INITIAL_BALANCE - LOCKED);
+    })
+}
+
+#[test]
+fn sponsored_call() {
+    use frame_support::unsigned::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError,
+    };
+
+    let sponsor_seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+    let sponsor_pair = ecdsa::Pair::from_seed(&sponsor_seed);
+    let sponsor = MultiSigner::from(sponsor_pair.public()).into_account();
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+        let _ = Balances::deposit_creating(&sponsor, INITIAL_BALANCE);
+
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let fee = CustomSignatures::query_call_fee(&call);
+        let payload = (0xff50u16, 0u32, call.clone(), sponsor.clone()).encode();
+        let signature = eth_sign(&ECDSA_SEED, &payload);
+        let sponsor_signature = eth_sign(
+            &sponsor_seed,
+            &CustomSignatures::sponsor_payload(&account, &payload),
+        );
+        let sponsored = |sponsor_signature: Option<Vec<u8>>| {
+            CustomSignatures::sponsored_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                PayloadVersion::V0,
+                0,
+                sponsor.clone(),
+                sponsor_signature,
+            )
+        };
+
+        // Sponsor pays only within allowance
+        assert_err!(
+            sponsored(Some(sponsor_signature.clone())),
+            Error::<Runtime>::SponsorAllowanceExceeded,
+        );
+        assert_ok!(CustomSignatures::set_sponsorship(
+            Origin::signed(sponsor.clone()),
+            fee * 3,
+            false,
+        ));
+
+        // Closed sponsorship requires sponsor signature
+        assert_err!(sponsored(None), Error::<Runtime>::NotSponsored);
+        assert_err!(sponsored(Some(signature.clone())), Error::<Runtime>::NotSponsored);
+        let validity = CustomSignatures::validate_unsigned(
+            TransactionSource::External,
+            &custom_signatures::Call::<Runtime>::sponsored_call {
+                call: Box::new(call.clone()),
+                signer: account.clone(),
+                signature: signature.clone(),
+                version: PayloadVersion::V0,
+                nonce: 0,
+                sponsor: sponsor.clone(),
+                sponsor_signature: None,
+            },
+        );
+        assert_eq!(
+            validity,
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Custom(NOT_SPONSORED))),
+        );
+
+        // Signer signature is bound to the sponsor
+        let plain_signature = eth_sign(&ECDSA_SEED, &(0xff50u16, 0u32, call.clone()).encode());
+        assert_err!(
+            CustomSignatures::sponsored_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                plain_signature.clone(),
+                PayloadVersion::V0,
+                0,
+                sponsor.clone(),
+                Some(eth_sign(
+                    &sponsor_seed,
+                    &CustomSignatures::sponsor_payload(
+                        &account,
+                        &(0xff50u16, 0u32, call.clone()).encode()
+                    ),
+                )),
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+        assert_err!(
+            CustomSignatures::versioned_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature.clone(),
+                PayloadVersion::V0,
+                0,
+            ),
+            Error::<Runtime>::InvalidSignature,
+        );
+        assert_eq!(
+            CustomSignatures::signed_sponsored_payload(&call, &sponsor, &0, &PayloadVersion::V0),
+            Ok(payload.clone()),
+        );
+
+        assert_ok!(sponsored(Some(sponsor_signature)));
+        assert_eq!(System::account(alice.clone()).data.free, 1_000);
+        assert_eq!(System::account(account.clone()).data.free, INITIAL_BALANCE - 1_000);
+        assert_eq!(System::account(sponsor.clone()).data.free, INITIAL_BALANCE - fee);
+        assert_eq!(CustomSignatures::sponsorship(&sponsor).allowance, fee * 2);
+        assert_eq!(
+            System::events().last().map(|r| r.event.clone()),
+            Some(Event::CustomSignatures(custom_signatures::Event::FeePaid(
+                account.clone(),
+                sponsor.clone(),
+                call.get_dispatch_info().weight,
+                fee,
+            ))),
+        );
+    })
+}
+
+#[test]
+fn open_sponsorship() {
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let sponsor: <Runtime as frame_system::Config>::AccountId = Keyring::Bob.into();
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+        let _ = Balances::deposit_creating(&sponsor, INITIAL_BALANCE);
+
+        let transfer: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let remark: Call = frame_system::Call::<Runtime>::remark { remark: vec![] }.into();
+        let fee = CustomSignatures::query_call_fee(&transfer);
+        assert_ok!(CustomSignatures::set_sponsorship(
+            Origin::signed(sponsor.clone()),
+            fee,
+            true,
+        ));
+
+        let sponsored = |call: &Call, nonce: u32| {
+            let payload = (0xff50u16, nonce, call.clone(), sponsor.clone());
+            let signature = eth_sign(&ECDSA_SEED, &payload.encode());
+            CustomSignatures::sponsored_call(
+                Origin::none(),
+                Box::new(call.clone()),
+                account.clone(),
+                signature,
+                PayloadVersion::V0,
+                nonce,
+                sponsor.clone(),
+                None,
+            )
+        };
+
+        // Only `SponsoredCalls` are paid by open sponsor
+        assert_err!(sponsored(&remark, 0), Error::<Runtime>::NotSponsored);
+        assert_ok!(sponsored(&transfer, 0));
+        assert_eq!(System::account(sponsor.clone()).data.free, INITIAL_BALANCE - fee);
+
+        // Exhausted sponsorship is removed
+        assert_eq!(CustomSignatures::sponsorship(&sponsor), Default::default());
+        assert_err!(sponsored(&transfer, 1), Error::<Runtime>::NotSponsored);
+    })
+}
+
+#[test]
+fn any_signature_vectors() {
+    use sp_core::ed25519;
+    use sp_runtime::AccountId32;
+    use sp_std::convert::TryFrom;
+
+    type AnySignature = multi::AnySignature<TestDomain>;
+
+    fn check(tag: u8, signature: &[u8], msg: &[u8], signer: &AccountId32) -> bool {
+        let mut data = vec![tag];
+        data.extend_from_slice(signature);
+        AnySignature::try_from(data)
+            .expect("valid tagged signature")
+            .verify(msg, signer)
+    }
+
+    let seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+    let account = MultiSigner::from(ecdsa::Pair::from_seed(&seed).public()).into_account();
+    let text = b"Hello Astar";
+
+    // Ethereum `personal_sign`
+    let personal_sign = hex!["0cc6d5de6db06727fe43a260e7c9a417be3daab9b0e4e65e276f543e5c2f3de67e9e26d903d5301181e13033f61692db2dca67c1f8992b62476eaf8cb3a597101c"];
+    assert!(check(0, &personal_sign, text, &account));
+
+    // EIP-712 typed data of payload with undecodable call, so fee is zero
+    let payload = hex!["50ff00000000ffff48656c6c6f204173746172"];
+    let typed_data = hex!["11fec80374aabd62a16bb5a08204329dc9d07513786a370cbcbe8c3e1e14e53c61008ef76475b36a103c11d5f30765de5fade6a1384ab92862f65c9e90ca66371c"];
+    assert!(check(1, &typed_data, &payload, &account));
+
+    // Raw secp256k1 signature of keccak-256 hash
+    let raw = hex!["8983dff858adbb1da788c422d8414e2687bf88572dc4ebdf3b57bc1b5bcd60370ef68b6685916c028c3c5d6f482453932a33f4b8233a77a546d9372043c3adef1c"];
+    assert!(check(2, &raw, text, &account));
+
+    // Ed25519, RFC 8032 test vector 1
+    let public = hex!["d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"];
+    let ed25519 = hex!["e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"];
+    let ed25519_account = MultiSigner::from(ed25519::Public::from_raw(public)).into_account();
+    assert!(check(3, &ed25519, b"", &ed25519_account));
+
+    // Signature is checked by the tagged scheme only
+    assert!(!check(0, &raw, text, &account));
+    assert!(!check(2, &personal_sign, text, &account));
+    assert!(!check(3, &ed25519, b"", &account));
+
+    // Unknown tag and trailing bytes are rejected
+    assert!(AnySignature::try_from([&[4u8][..], &raw[..]].concat()).is_err());
+    assert!(AnySignature::try_from([&[3u8][..], &raw[..]].concat()).is_err());
+
+    // Untagged signatures of legacy clients are still accepted
+    let legacy = |signature: &[u8]| AnySignature::try_from(signature.to_vec()).unwrap();
+    assert!(legacy(&personal_sign).verify(&text[..], &account));
+    assert!(legacy(&typed_data).verify(&payload[..], &account));
+    assert!(!legacy(&raw).verify(&text[..], &account));
+    let tagged_ed25519 = [&[3u8][..], &ed25519[..]].concat();
+    assert_eq!(
+        AnySignature::try_from(tagged_ed25519),
+        Ok(AnySignature::Ed25519(ed25519::Signature::from_raw(ed25519))),
+    );
+
+    // Legacy `v` of 0/1 notation is normalised to 27/28 one
+    let mut recovery_id = personal_sign;
+    recovery_id[64] -= 27;
+    assert_eq!(legacy(&recovery_id), legacy(&personal_sign));
+    assert!(legacy(&recovery_id).verify(&text[..], &account));
+
+    // but tagged ed25519 signature ending with 0/1 stays as it is
+    let mut ed25519_low = ed25519;
+    ed25519_low[63] = 1;
+    let tagged_ed25519 = [&[3u8][..], &ed25519_low[..]].concat();
+    assert_eq!(
+        AnySignature::try_from(tagged_ed25519),
+        Ok(AnySignature::Ed25519(ed25519::Signature::from_raw(ed25519_low))),
+    );
+}
+
+#[test]
+fn unsigned_validity_follows_nonce() {
+    use frame_support::{traits::UnfilteredDispatchable, unsigned::ValidateUnsigned};
+    use sp_runtime::traits::SignedExtension;
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError,
+    };
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: Keyring::Alice.into(),
+            value: 1_000,
+        }
+        .into();
+        let signed_call = |nonce: u32| custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature: eth_sign(&ECDSA_SEED, &(0xff50u16, nonce, call.clone()).encode()),
+            nonce,
+        };
+        let validate = |nonce: u32| {
+            CustomSignatures::validate_unsigned(TransactionSource::External, &signed_call(nonce))
+        };
+
+        // Current nonce requires nothing, priority grows with the fee
+        let current = validate(0).expect("valid transaction");
+        assert!(current.requires.is_empty());
+        assert!(current.priority > Priority::get());
+
+        // Tags are the same as of signed transaction with the same nonce
+        let check_nonce = frame_system::CheckNonce::<Runtime>::from(0)
+            .validate(&account, &call, &call.get_dispatch_info(), 0)
+            .expect("valid signed transaction");
+        assert_eq!(current.provides, check_nonce.provides);
+        let check_nonce = frame_system::CheckNonce::<Runtime>::from(1)
+            .validate(&account, &call, &call.get_dispatch_info(), 0)
+            .expect("valid signed transaction");
+
+        // Future nonce waits for the previous one
+        let future = validate(1).expect("valid future transaction");
+        assert_eq!(future.requires, current.provides);
+        assert_eq!(future.requires, check_nonce.requires);
+        assert_eq!(future.provides, check_nonce.provides);
+        assert_eq!(future.priority, current.priority);
+
+        // Future nonce can't be dispatched before the previous one
+        assert_err!(
+            signed_call(1).dispatch_bypass_filter(Origin::none()),
+            Error::<Runtime>::BadNonce,
+        );
+        assert_ok!(signed_call(0).dispatch_bypass_filter(Origin::none()));
+        assert_eq!(
+            validate(0),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Stale)),
+        );
+        assert!(validate(1).expect("valid transaction").requires.is_empty());
+    })
+}
+
+#[test]
+fn unpaid_call_is_invalid() {
+    use frame_support::{traits::UnfilteredDispatchable, unsigned::ValidateUnsigned};
+    use sp_runtime::transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidityError,
+    };
+
+    let seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&seed);
+        let account = MultiSigner::from(pair.public()).into_account();
+        let call: Call = frame_system::Call::<Runtime>::remark { remark: vec![] }.into();
+        let signed_call = custom_signatures::Call::<Runtime>::call {
+            call: Box::new(call.clone()),
+            signer: account.clone(),
+            signature: eth_sign(&seed, &(0xff50u16, 0u32, call.clone()).encode()),
+            nonce: 0,
+        };
+        let validate =
+            || CustomSignatures::validate_unsigned(TransactionSource::External, &signed_call);
+
+        // Signature is valid, but the signer has nothing to pay the fee with
+        assert_eq!(
+            validate(),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Payment)),
+        );
+        assert_noop!(
+            signed_call.clone().dispatch_bypass_filter(Origin::none()),
+            pallet_balances::Error::<Runtime>::InsufficientBalance,
+        );
+        assert_eq!(System::account_nonce(&account), 0);
+
+        // Validation doesn't withdraw the fee
+        let fee = CustomSignatures::query_call_fee(&call);
+        let _ = Balances::deposit_creating(&account, fee);
+        assert!(validate().is_ok());
+        assert_eq!(Balances::free_balance(&account), fee);
+    })
+}
+
+#[test]
+fn signer_helpers() {
+    let seed = hex!["ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80"];
+    let pair = ecdsa::Pair::from_seed(&seed);
+    assert_eq!(
+        signer::address(&pair),
+        hex!["f39fd6e51aad88f6f4ce6ab8827279cfffb92266"].into(),
+    );
+    assert_eq!(signer::eth_sign(&pair, b"Hello Astar").to_vec(), eth_sign(&seed, b"Hello Astar"));
+
+    new_test_ext().execute_with(|| {
+        let pair = ecdsa::Pair::from_seed(&ECDSA_SEED);
+        let alice: <Runtime as frame_system::Config>::AccountId = Keyring::Alice.into();
+        let call: Call = pallet_balances::Call::<Runtime>::transfer {
+            dest: alice.clone(),
+            value: 1_000,
+        }
+        .into();
+        let payload = signer::payload(0xff50, 0, &call, None);
+        let signature = signer::eip712_sign::<TestDomain>(&pair, &payload).unwrap();
+        assert_ok!(CustomSignatures::call(
+            Origin::none(),
+            Box::new(call.clone()),
+            signer::account(&pair),
+            signature.to_vec(),
+            0,
+        ));
+        assert_eq!(System::account(alice.clone()).data.free, 1_000);
+
+        let payload = signer::batch_payload(0xff50, 1, &[call.clone()], true, None);
+        assert_ok!(CustomSignatures::call_batch(
+            Origin::none(),
+            vec![call.clone()],
+            true,
+            signer::account(&pair),
+            signer::eth_sign(&pair, &payload).to_vec(),
+            PayloadVersion::V0,
+            1,
+        ));
+        assert_eq!(System::account(alice.clone()).data.free, 2_000);
+
+        let sponsor: AccountId = Keyring::Bob.into();
+        assert_eq!(
+            signer::sponsored_payload(0xff50, 2, &call, &sponsor, None),
+            CustomSignatures::signed_sponsored_payload(&call, &sponsor, &2, &PayloadVersion::V0)
+                .unwrap(),
+        );
+    })
+}
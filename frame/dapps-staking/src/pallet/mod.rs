@@ -49,6 +49,15 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> pallet_block_reward_primitives::TvlProvider<BalanceOf<T>> for Pallet<T> {
+        /// Total amount staked on dapps in the current era.
+        fn tvl() -> BalanceOf<T> {
+            Self::era_reward_and_stake(Self::current_era())
+                .map(|x| x.staked)
+                .unwrap_or_default()
+        }
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// The staking balance.
@@ -4,6 +4,7 @@ use frame_support::{
     traits::{OnInitialize, OnUnbalanced},
 };
 use mock::{Balances, MockSmartContract, *};
+use pallet_block_reward_primitives::TvlProvider;
 use sp_core::H160;
 use sp_runtime::traits::Zero;
 
@@ -116,6 +117,26 @@ fn staking_info_is_ok() {
     })
 }
 
+#[test]
+fn tvl_is_ok() {
+    ExternalityBuilder::build().execute_with(|| {
+        initialize_first_block();
+        assert!(<DappsStaking as TvlProvider<Balance>>::tvl().is_zero());
+
+        let contract_id = MockSmartContract::Evm(H160::repeat_byte(0x01));
+        register_contract(10, &contract_id);
+
+        bond_and_stake_with_verification(1, &contract_id, 100);
+        bond_and_stake_with_verification(2, &contract_id, 200);
+        assert_eq!(300, <DappsStaking as TvlProvider<Balance>>::tvl());
+
+        // Staked value is carried over to the next era
+        advance_to_era(DappsStaking::current_era() + 1);
+        unbond_unstake_and_withdraw_with_verification(2, &contract_id, 50);
+        assert_eq!(250, <DappsStaking as TvlProvider<Balance>>::tvl());
+    })
+}
+
 #[test]
 fn register_is_ok() {
     ExternalityBuilder::build().execute_with(|| {
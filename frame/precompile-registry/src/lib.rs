@@ -0,0 +1,308 @@
+//! # Precompile Registry Pallet
+//!
+//! - [`Config`]
+//! - [`precompile_set!`]
+//!
+//! ## Overview
+//!
+//! EVM precompiles of the runtime are declared once by `precompile_set!` table of addresses
+//! and precompile types. The table gives both the `PrecompileSet` of `pallet_evm` and the list
+//! of precompile addresses, so they can't go out of sync.
+//!
+//! EVM calls an address only when it has some code, this pallet keeps `DUMMY_CODE` under
+//! each precompile address: it's inserted at genesis and on every runtime upgrade, so new
+//! precompiles are callable from contracts right away.
+//!
+//! Precompile could be disabled by root without runtime upgrade, calls to the disabled
+//! precompile execute the dummy code, i.e. revert.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `disable_precompile` - Disable precompile at given address.
+//! - `enable_precompile` - Enable previously disabled precompile.
+//!
+//! ## Usage
+//!
+//! 1. Declare precompiles of the runtime by `precompile_set!`.
+//! 2. Use the declared set as `Precompiles` of both `pallet_evm` and this pallet.
+//! 3. Include pallet into runtime with `Pallet`, `Call`, `Storage`, `Event` and `Config` parts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::H160;
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Code under precompile addresses, it's the simplest code to revert without data.
+/// (PUSH1 0x00 PUSH1 0x00 REVERT)
+pub const DUMMY_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xFD];
+
+/// Addresses of the declared precompiles.
+pub trait PrecompileAddresses {
+    /// Addresses of all precompiles, enabled or not.
+    fn used_addresses() -> Vec<H160>;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    pub use evm::{executor::PrecompileOutput, Context, ExitError};
+    pub use pallet_evm::{Precompile, PrecompileSet};
+    pub use sp_core::H160;
+    pub use sp_std::{marker::PhantomData, vec, vec::Vec};
+}
+
+/// Declare precompile set of the runtime by the table of precompile addresses and types.
+///
+/// It generates the precompile set struct that implements both `PrecompileSet` and
+/// `PrecompileAddresses`. Precompiles disabled in the pallet aren't executed, the check is
+/// charged to every precompile call as a single storage read.
+///
+/// ```ignore
+/// precompile_set! {
+///     /// The PrecompileSet installed in the runtime.
+///     pub struct NetworkPrecompiles<R> {
+///         1 => ECRecover,
+///         20480 => Staking<R>,
+///     }
+///     where
+///         R: pallet_evm::Config + pallet_collator_selection::Config,
+/// }
+/// ```
+#[macro_export]
+macro_rules! precompile_set {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$runtime:ident> {
+            $( $address:literal => $precompile:ty ),* $(,)?
+        }
+        $( where $( $bounds:tt )* )?
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        $vis struct $name<$runtime>($crate::__private::PhantomData<$runtime>);
+
+        impl<$runtime> $crate::PrecompileAddresses for $name<$runtime> {
+            fn used_addresses() -> $crate::__private::Vec<$crate::__private::H160> {
+                $crate::__private::vec![
+                    $( $crate::__private::H160::from_low_u64_be($address) ),*
+                ]
+            }
+        }
+
+        impl<$runtime> $crate::__private::PrecompileSet for $name<$runtime>
+        where
+            $runtime: $crate::Config,
+            $( $( $bounds )* )?
+        {
+            fn execute(
+                address: $crate::__private::H160,
+                input: &[u8],
+                target_gas: Option<u64>,
+                context: &$crate::__private::Context,
+            ) -> Option<
+                Result<$crate::__private::PrecompileOutput, $crate::__private::ExitError>,
+            > {
+                // Precompile addresses are 64-bit numbers
+                let index = address.to_low_u64_be();
+                if address != $crate::__private::H160::from_low_u64_be(index) {
+                    return None;
+                }
+
+                let execute: fn(
+                    &[u8],
+                    Option<u64>,
+                    &$crate::__private::Context,
+                ) -> Result<$crate::__private::PrecompileOutput, $crate::__private::ExitError> =
+                    match index {
+                        $( $address => <$precompile as $crate::__private::Precompile>::execute, )*
+                        _ => return None,
+                    };
+
+                // Check of disabled precompiles is charged as a storage read
+                let check_cost = $crate::Pallet::<$runtime>::is_disabled_gas_cost();
+                let target_gas = match target_gas {
+                    Some(gas) if gas < check_cost => {
+                        return Some(Err($crate::__private::ExitError::OutOfGas));
+                    }
+                    Some(gas) => Some(gas - check_cost),
+                    None => None,
+                };
+                if $crate::Pallet::<$runtime>::is_disabled(address) {
+                    return None;
+                }
+                Some(execute(input, target_gas, context).map(|mut output| {
+                    output.cost = output.cost.saturating_add(check_cost);
+                    output
+                }))
+            }
+        }
+    };
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::{PrecompileAddresses, DUMMY_CODE};
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+    use pallet_evm::GasWeightMapping;
+    use sp_core::H160;
+    use sp_std::prelude::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_evm::Config {
+        /// The overarching event type.
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+        /// Precompile set declared by `precompile_set!`.
+        type Precompiles: PrecompileAddresses;
+    }
+
+    /// Weight of a single address comparison in the lookup of declared precompiles.
+    pub const ADDRESS_LOOKUP_WEIGHT: Weight = 100_000;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    /// Precompiles disabled by root.
+    #[pallet::storage]
+    pub type DisabledPrecompiles<T: Config> = StorageMap<_, Twox64Concat, H160, ()>;
+
+    #[pallet::genesis_config]
+    pub struct GenesisConfig {
+        /// Precompiles disabled from the genesis.
+        pub disabled: Vec<H160>,
+    }
+
+    #[cfg(feature = "std")]
+    impl Default for GenesisConfig {
+        fn default() -> Self {
+            Self { disabled: vec![] }
+        }
+    }
+
+    #[pallet::genesis_build]
+    impl<T: Config> GenesisBuild<T> for GenesisConfig {
+        fn build(&self) {
+            Pallet::<T>::insert_dummy_code();
+            for address in &self.disabled {
+                DisabledPrecompiles::<T>::insert(address, ());
+            }
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(crate) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Precompile disabled. \[address\]
+        PrecompileDisabled(H160),
+        /// Precompile enabled. \[address\]
+        PrecompileEnabled(H160),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// There is no precompile at the address.
+        UnknownPrecompile,
+        /// Precompile is already disabled.
+        AlreadyDisabled,
+        /// Precompile isn't disabled.
+        NotDisabled,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            Self::insert_dummy_code()
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Disable precompile at `address`, calls to it are reverted.
+        ///
+        /// The dispatch origin must be Root.
+        ///
+        /// # <weight>
+        /// - O(P) where P is the number of precompiles.
+        /// - One DB read and one write (disabled precompiles).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(
+            ADDRESS_LOOKUP_WEIGHT
+                .saturating_mul(<T as Config>::Precompiles::used_addresses().len() as Weight)
+                .saturating_add(T::DbWeight::get().reads_writes(1, 2))
+        )]
+        pub fn disable_precompile(
+            origin: OriginFor<T>,
+            address: H160,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                <T as Config>::Precompiles::used_addresses().contains(&address),
+                Error::<T>::UnknownPrecompile
+            );
+            ensure!(!Self::is_disabled(address), Error::<T>::AlreadyDisabled);
+
+            DisabledPrecompiles::<T>::insert(address, ());
+
+            Self::deposit_event(Event::<T>::PrecompileDisabled(address));
+            Ok(().into())
+        }
+
+        /// Enable precompile at `address` that has been disabled.
+        ///
+        /// The dispatch origin must be Root.
+        ///
+        /// # <weight>
+        /// - O(1).
+        /// - One DB read and one write (disabled precompiles).
+        /// - One DB write (event).
+        /// # </weight>
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 2))]
+        pub fn enable_precompile(
+            origin: OriginFor<T>,
+            address: H160,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(Self::is_disabled(address), Error::<T>::NotDisabled);
+
+            DisabledPrecompiles::<T>::remove(address);
+
+            Self::deposit_event(Event::<T>::PrecompileEnabled(address));
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Whether precompile at `address` is disabled.
+        pub fn is_disabled(address: H160) -> bool {
+            DisabledPrecompiles::<T>::contains_key(address)
+        }
+
+        /// Gas cost of `is_disabled` check, it's a single storage read.
+        pub fn is_disabled_gas_cost() -> u64 {
+            <T as pallet_evm::Config>::GasWeightMapping::weight_to_gas(T::DbWeight::get().read)
+        }
+
+        /// Insert `DUMMY_CODE` under precompile addresses that have no code yet.
+        pub(crate) fn insert_dummy_code() -> Weight {
+            let addresses = <T as Config>::Precompiles::used_addresses();
+            let mut inserted = 0u64;
+            for address in &addresses {
+                if !pallet_evm::AccountCodes::<T>::contains_key(address) {
+                    pallet_evm::AccountCodes::<T>::insert(address, DUMMY_CODE.to_vec());
+                    inserted += 1;
+                }
+            }
+            T::DbWeight::get().reads_writes(addresses.len() as u64, inserted)
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::{pallet::Error, Event, *};
+use evm::{executor::PrecompileOutput, Context, ExitError};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Get, Hooks},
+    weights::{constants::RocksDbWeight, GetDispatchInfo},
+};
+use mock::*;
+use pallet_evm::PrecompileSet;
+use sp_core::U256;
+use sp_runtime::traits::BadOrigin;
+
+fn precompile(index: u64) -> H160 {
+    H160::from_low_u64_be(index)
+}
+
+fn execute_with_gas(
+    address: H160,
+    input: &[u8],
+    target_gas: Option<u64>,
+) -> Option<Result<PrecompileOutput, ExitError>> {
+    let context = Context {
+        address,
+        caller: H160::repeat_byte(0xAA),
+        apparent_value: U256::zero(),
+    };
+    TestPrecompiles::<TestRuntime>::execute(address, input, target_gas, &context)
+}
+
+fn execute(address: H160, input: &[u8]) -> Option<Vec<u8>> {
+    execute_with_gas(address, input, None)
+        .map(|result| result.expect("echo precompile never fails").output)
+}
+
+#[test]
+fn precompile_set_is_declared_by_table() {
+    ExternalityBuilder::build(vec![]).execute_with(|| {
+        assert_eq!(
+            TestPrecompiles::<TestRuntime>::used_addresses(),
+            vec![precompile(ECHO), precompile(ANOTHER_ECHO)],
+        );
+
+        assert_eq!(execute(precompile(ECHO), b"ping"), Some(b"ping".to_vec()));
+        assert_eq!(execute(precompile(ANOTHER_ECHO), b"pong"), Some(b"pong".to_vec()));
+        assert_eq!(execute(precompile(2), b"ping"), None);
+
+        // Low bytes match, but it isn't precompile address
+        let mut address = precompile(ECHO);
+        address.0[0] = 1;
+        assert_eq!(execute(address, b"ping"), None);
+    })
+}
+
+#[test]
+fn dummy_code_is_inserted() {
+    ExternalityBuilder::build(vec![]).execute_with(|| {
+        for index in [ECHO, ANOTHER_ECHO] {
+            assert_eq!(
+                pallet_evm::AccountCodes::<TestRuntime>::get(precompile(index)),
+                DUMMY_CODE.to_vec(),
+            );
+        }
+
+        // Lost code is restored on runtime upgrade, existing one is kept
+        pallet_evm::AccountCodes::<TestRuntime>::remove(precompile(ECHO));
+        pallet_evm::AccountCodes::<TestRuntime>::insert(precompile(ANOTHER_ECHO), vec![0x00]);
+        PrecompileRegistry::on_runtime_upgrade();
+        assert_eq!(
+            pallet_evm::AccountCodes::<TestRuntime>::get(precompile(ECHO)),
+            DUMMY_CODE.to_vec(),
+        );
+        assert_eq!(
+            pallet_evm::AccountCodes::<TestRuntime>::get(precompile(ANOTHER_ECHO)),
+            vec![0x00],
+        );
+    })
+}
+
+#[test]
+fn disable_and_enable_are_ok() {
+    ExternalityBuilder::build(vec![precompile(ANOTHER_ECHO)]).execute_with(|| {
+        assert_eq!(execute(precompile(ANOTHER_ECHO), b"ping"), None);
+
+        assert_ok!(PrecompileRegistry::disable_precompile(
+            Origin::root(),
+            precompile(ECHO),
+        ));
+        assert!(PrecompileRegistry::is_disabled(precompile(ECHO)));
+        assert_eq!(execute(precompile(ECHO), b"ping"), None);
+
+        assert_ok!(PrecompileRegistry::enable_precompile(
+            Origin::root(),
+            precompile(ECHO),
+        ));
+        assert!(!PrecompileRegistry::is_disabled(precompile(ECHO)));
+        assert_eq!(execute(precompile(ECHO), b"ping"), Some(b"ping".to_vec()));
+
+        assert_eq!(
+            precompile_registry_events(),
+            vec![
+                Event::PrecompileDisabled(precompile(ECHO)),
+                Event::PrecompileEnabled(precompile(ECHO)),
+            ]
+        );
+    })
+}
+
+#[test]
+fn disable_and_enable_fail() {
+    ExternalityBuilder::build(vec![precompile(ANOTHER_ECHO)]).execute_with(|| {
+        let account = AccountId::new([1; 32]);
+        assert_noop!(
+            PrecompileRegistry::disable_precompile(Origin::signed(account), precompile(ECHO)),
+            BadOrigin,
+        );
+        assert_noop!(
+            PrecompileRegistry::disable_precompile(Origin::root(), precompile(2)),
+            Error::<TestRuntime>::UnknownPrecompile,
+        );
+        assert_noop!(
+            PrecompileRegistry::disable_precompile(Origin::root(), precompile(ANOTHER_ECHO)),
+            Error::<TestRuntime>::AlreadyDisabled,
+        );
+        assert_noop!(
+            PrecompileRegistry::enable_precompile(Origin::root(), precompile(ECHO)),
+            Error::<TestRuntime>::NotDisabled,
+        );
+    })
+}
+
+#[test]
+fn disabled_check_is_charged() {
+    ExternalityBuilder::build(vec![]).execute_with(|| {
+        let check_cost = PrecompileRegistry::is_disabled_gas_cost();
+        assert!(check_cost > 0);
+
+        let output = execute_with_gas(precompile(ECHO), b"ping", None).unwrap().unwrap();
+        assert_eq!(output.cost, check_cost);
+        let output = execute_with_gas(precompile(ECHO), b"ping", Some(check_cost))
+            .unwrap()
+            .unwrap();
+        assert_eq!(output.cost, check_cost);
+
+        assert_eq!(
+            execute_with_gas(precompile(ECHO), b"ping", Some(check_cost - 1)),
+            Some(Err(ExitError::OutOfGas))
+        );
+    })
+}
+
+#[test]
+fn disable_weight_scales_with_precompiles() {
+    ExternalityBuilder::build(vec![]).execute_with(|| {
+        let call = pallet::Call::<TestRuntime>::disable_precompile {
+            address: precompile(ECHO),
+        };
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            ADDRESS_LOOKUP_WEIGHT * 2 + RocksDbWeight::get().reads_writes(1, 2)
+        );
+    })
+}
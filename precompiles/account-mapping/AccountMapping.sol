@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: BSD-3-Clause
+
+pragma solidity >=0.7.0;
+
+interface AccountMapping {
+    /*
+     * @dev Substrate account of the address as EVM maps it.
+     */
+    function mapped_account(address addr) external view returns (bytes32);
+
+    /*
+     * @dev Address bound to the account in the registry, zero if not bound.
+     */
+    function bound_address(bytes32 account) external view returns (address);
+
+    /*
+     * @dev Account bound to the address in the registry, zero if not bound.
+     */
+    function bound_account(address addr) external view returns (bytes32);
+
+    /*
+     * @dev Check claim that account belongs to the address, i.e. the account is mapped from
+     * the address or the address is bound to the account in the registry.
+     */
+    function verify_claim(address addr, bytes32 account) external view returns (bool);
+}
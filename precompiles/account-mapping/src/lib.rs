@@ -0,0 +1,138 @@
+//! Ethereum address to Substrate account mapping.
+//!
+//! EVM maps address to account by `pallet_evm::Config::AddressMapping`, accounts could also
+//! bind preferred address in `pallet_address_registry`. Both are exposed to contracts.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use pallet_evm::{AddressMapping, Precompile};
+use precompile_utils::{
+    revert, selector, Address, EvmDataReader, EvmDataWriter, EvmResult, Gasometer, RuntimeHelper,
+};
+use sp_core::H256;
+use sp_std::{marker::PhantomData, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// ======= AccountMapping.sol:AccountMapping =======
+const MAPPED_ACCOUNT: u32 = selector!("mapped_account(address)");
+const BOUND_ADDRESS: u32 = selector!("bound_address(bytes32)");
+const BOUND_ACCOUNT: u32 = selector!("bound_account(address)");
+const VERIFY_CLAIM: u32 = selector!("verify_claim(address,bytes32)");
+
+/// Gas cost of precompile call, address mapping and storage reads are charged on top.
+pub const BASE_COST: u64 = 500;
+
+/// Gas cost of address mapping, the same as SHA256 of one word.
+const MAPPING_COST: u64 = 72;
+
+pub struct AccountMapping<R>(PhantomData<R>);
+
+impl<R> AccountMapping<R>
+where
+    R: pallet_evm::Config + pallet_address_registry::Config,
+    R::AccountId: AsRef<[u8; 32]> + From<[u8; 32]>,
+{
+    /// Successful output of view function.
+    fn output(gasometer: &Gasometer, output: Vec<u8>) -> PrecompileOutput {
+        PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output,
+            logs: Default::default(),
+        }
+    }
+
+    fn mapped_account(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let address: Address = input.read()?;
+
+        gasometer.record_cost(MAPPING_COST)?;
+        let account = R::AddressMapping::into_account_id(address.0);
+        let output = EvmDataWriter::new()
+            .write(H256::from(*account.as_ref()))
+            .build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn bound_address(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let account: H256 = input.read()?;
+        let account = R::AccountId::from(account.0);
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let address = pallet_address_registry::Pallet::<R>::address_of(account).unwrap_or_default();
+        let output = EvmDataWriter::new().write(Address(address)).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn bound_account(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let address: Address = input.read()?;
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let account = pallet_address_registry::Pallet::<R>::account_of(address.0)
+            .map(|account| H256::from(*account.as_ref()))
+            .unwrap_or_default();
+        let output = EvmDataWriter::new().write(account).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn verify_claim(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(2)?;
+        let address: Address = input.read()?;
+        let account: H256 = input.read()?;
+        let account = R::AccountId::from(account.0);
+
+        gasometer.record_cost(MAPPING_COST)?;
+        let valid = if R::AddressMapping::into_account_id(address.0) == account {
+            true
+        } else {
+            gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+            pallet_address_registry::Pallet::<R>::address_of(account) == Some(address.0)
+        };
+        let output = EvmDataWriter::new().write(valid).build();
+        Ok(Self::output(gasometer, output))
+    }
+}
+
+impl<R> Precompile for AccountMapping<R>
+where
+    R: pallet_evm::Config + pallet_address_registry::Config,
+    R::AccountId: AsRef<[u8; 32]> + From<[u8; 32]>,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+    ) -> Result<PrecompileOutput, ExitError> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(BASE_COST)?;
+        gasometer.record_input_cost(input)?;
+        let (mut input, selector) = EvmDataReader::new_with_selector(input)?;
+
+        match selector {
+            MAPPED_ACCOUNT => Self::mapped_account(&mut input, &mut gasometer),
+            BOUND_ADDRESS => Self::bound_address(&mut input, &mut gasometer),
+            BOUND_ACCOUNT => Self::bound_account(&mut input, &mut gasometer),
+            VERIFY_CLAIM => Self::verify_claim(&mut input, &mut gasometer),
+            _ => Err(revert("No method at given selector")),
+        }
+    }
+}
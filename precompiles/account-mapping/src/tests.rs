@@ -0,0 +1,146 @@
+use super::*;
+use frame_support::{traits::Get, weights::constants::RocksDbWeight};
+use mock::*;
+use pallet_evm::GasWeightMapping;
+use precompile_utils::INPUT_WORD_COST;
+use sp_core::{H160, U256};
+
+fn call(input: &[u8], target_gas: Option<u64>) -> EvmResult<PrecompileOutput> {
+    let context = Context {
+        address: PRECOMPILE,
+        caller: NOBODY,
+        apparent_value: U256::zero(),
+    };
+    AccountMapping::<TestRuntime>::execute(input, target_gas, &context)
+}
+
+/// Base cost and input cost charged for every call.
+fn call_cost(input: &[u8]) -> u64 {
+    BASE_COST + (input.len() as u64 + 31) / 32 * INPUT_WORD_COST
+}
+
+fn read_cost() -> u64 {
+    RuntimeHelper::<TestRuntime>::db_read_gas_cost()
+}
+
+fn to_h256(account: AccountId) -> H256 {
+    H256::from(*account.as_ref())
+}
+
+#[test]
+fn mapped_account_is_address_mapping() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = EvmDataWriter::new_with_selector(MAPPED_ACCOUNT)
+            .write(Address(NOBODY))
+            .build();
+        let output = call(&input, None).unwrap();
+
+        assert_eq!(output.cost, call_cost(&input) + MAPPING_COST);
+        assert_eq!(
+            output.output,
+            EvmDataWriter::new().write(to_h256(account(NOBODY))).build()
+        );
+    })
+}
+
+#[test]
+fn bound_address_and_account_are_charged_storage_read() {
+    ExternalityBuilder::build().execute_with(|| {
+        // Storage read is weighted by the runtime `DbWeight`
+        assert_eq!(
+            read_cost(),
+            <TestRuntime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+                RocksDbWeight::get().reads(1)
+            )
+        );
+
+        let input = EvmDataWriter::new_with_selector(BOUND_ADDRESS)
+            .write(H256::from(BOUND_ACCOUNT_ID))
+            .build();
+        let output = call(&input, None).unwrap();
+        assert_eq!(output.cost, call_cost(&input) + read_cost());
+        assert_eq!(output.output, EvmDataWriter::new().write(Address(BOUND)).build());
+
+        let input = EvmDataWriter::new_with_selector(BOUND_ACCOUNT)
+            .write(Address(BOUND))
+            .build();
+        let output = call(&input, None).unwrap();
+        assert_eq!(output.cost, call_cost(&input) + read_cost());
+        assert_eq!(
+            output.output,
+            EvmDataWriter::new().write(H256::from(BOUND_ACCOUNT_ID)).build()
+        );
+    })
+}
+
+#[test]
+fn missing_binding_is_zero() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = EvmDataWriter::new_with_selector(BOUND_ADDRESS)
+            .write(to_h256(account(NOBODY)))
+            .build();
+        let output = call(&input, None).unwrap();
+        assert_eq!(
+            output.output,
+            EvmDataWriter::new().write(Address(H160::zero())).build()
+        );
+
+        let input = EvmDataWriter::new_with_selector(BOUND_ACCOUNT)
+            .write(Address(NOBODY))
+            .build();
+        let output = call(&input, None).unwrap();
+        assert_eq!(output.output, EvmDataWriter::new().write(H256::zero()).build());
+    })
+}
+
+#[test]
+fn verify_claim_accepts_mapped_and_bound_accounts() {
+    ExternalityBuilder::build().execute_with(|| {
+        let verify = |address: H160, account: H256| {
+            let input = EvmDataWriter::new_with_selector(VERIFY_CLAIM)
+                .write(Address(address))
+                .write(account)
+                .build();
+            call(&input, None).unwrap()
+        };
+        let valid = |valid: bool| EvmDataWriter::new().write(valid).build();
+        // Selector and two words of arguments
+        let call_cost = call_cost(&[0u8; 4 + 2 * 32]);
+
+        // Mapped account doesn't read storage
+        let output = verify(NOBODY, to_h256(account(NOBODY)));
+        assert_eq!(output.cost, call_cost + MAPPING_COST);
+        assert_eq!(output.output, valid(true));
+
+        let output = verify(BOUND, H256::from(BOUND_ACCOUNT_ID));
+        assert_eq!(output.cost, call_cost + MAPPING_COST + read_cost());
+        assert_eq!(output.output, valid(true));
+
+        let output = verify(NOBODY, H256::from(BOUND_ACCOUNT_ID));
+        assert_eq!(output.cost, call_cost + MAPPING_COST + read_cost());
+        assert_eq!(output.output, valid(false));
+    })
+}
+
+#[test]
+fn not_enough_gas() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = EvmDataWriter::new_with_selector(BOUND_ACCOUNT)
+            .write(Address(BOUND))
+            .build();
+        assert_eq!(
+            call(&input, Some(call_cost(&input) + read_cost() - 1)),
+            Err(ExitError::OutOfGas)
+        );
+    })
+}
+
+#[test]
+fn unknown_selector_reverts() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_eq!(
+            call(&[0u8; 4], None),
+            Err(ExitError::Other("No method at given selector".into()))
+        );
+    })
+}
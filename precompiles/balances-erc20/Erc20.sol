@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: BSD-3-Clause
+
+pragma solidity >=0.7.0;
+
+/*
+ * @dev ERC-20 interface of the native currency.
+ * @note Changes made by transfer, approve and transferFrom are NOT reverted
+ * when the calling frame reverts.
+ */
+interface Erc20 {
+    /*
+     * @dev Name of the native currency.
+     */
+    function name() external view returns (string memory);
+
+    /*
+     * @dev Symbol of the native currency.
+     */
+    function symbol() external view returns (string memory);
+
+    /*
+     * @dev Number of decimals of the native currency.
+     */
+    function decimals() external view returns (uint8);
+
+    /*
+     * @dev Total issuance of the native currency.
+     */
+    function totalSupply() external view returns (uint256);
+
+    /*
+     * @dev Free balance of the account.
+     */
+    function balanceOf(address owner) external view returns (uint256);
+
+    /*
+     * @dev Amount that spender is allowed to transfer from the owner account.
+     */
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    /*
+     * @dev Transfer value from function caller to the account.
+     * @note Transfer isn't subject to runtime call filter, as EVM value transfer.
+     */
+    function transfer(address to, uint256 value) external returns (bool);
+
+    /*
+     * @dev Allow spender to transfer up to value from function caller account.
+     */
+    function approve(address spender, uint256 value) external returns (bool);
+
+    /*
+     * @dev Transfer value from the account using allowance of function caller.
+     * @note Allowance is required even if function caller is the account.
+     */
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+}
@@ -0,0 +1,338 @@
+//! ERC-20 interface of the native currency.
+//!
+//! Balances are taken from `pallet_balances`, allowances are kept in the pallet storage.
+//! Transfers are done by `Currency::transfer` of `pallet_balances` the same way as EVM value
+//! transfers, i.e. they aren't subject to runtime call filter.
+//!
+//! # Warning
+//!
+//! `transfer`, `approve` and `transferFrom` write balances and allowances right into the
+//! runtime storage, the EVM state journal doesn't track them. If the calling EVM frame reverts
+//! after successful call of the precompile (e.g. contract calls `transfer` and then reverts),
+//! the transfer and allowance changes are NOT reverted. Contracts must not rely on revert to
+//! undo these calls.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::traits::{Currency, ExistenceRequirement};
+use pallet_balances::WeightInfo;
+use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
+use precompile_utils::{
+    keccak256, log3, revert, selector, Address, Bytes, EvmDataReader, EvmDataWriter, EvmResult,
+    Gasometer, RuntimeHelper,
+};
+use sp_core::{H160, U256};
+use sp_runtime::traits::{CheckedSub, Zero};
+use sp_std::{
+    convert::{TryFrom, TryInto},
+    marker::PhantomData,
+    string::String,
+    vec,
+    vec::Vec,
+};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// ======= Erc20.sol:Erc20 =======
+const NAME: u32 = selector!("name()");
+const SYMBOL: u32 = selector!("symbol()");
+const DECIMALS: u32 = selector!("decimals()");
+const TOTAL_SUPPLY: u32 = selector!("totalSupply()");
+const BALANCE_OF: u32 = selector!("balanceOf(address)");
+const ALLOWANCE: u32 = selector!("allowance(address,address)");
+const TRANSFER: u32 = selector!("transfer(address,uint256)");
+const APPROVE: u32 = selector!("approve(address,uint256)");
+const TRANSFER_FROM: u32 = selector!("transferFrom(address,address,uint256)");
+
+const TRANSFER_TOPIC: [u8; 32] = keccak256!("Transfer(address,address,uint256)");
+const APPROVAL_TOPIC: [u8; 32] = keccak256!("Approval(address,address,uint256)");
+
+/// Gas cost of precompile call, transfer weight and storage access are charged on top.
+pub const BASE_COST: u64 = 500;
+
+/// ERC-20 metadata of the native currency.
+pub trait Erc20Metadata {
+    /// Currency name, e.g. `Shiden`.
+    fn name() -> &'static str;
+    /// Currency symbol, e.g. `SDN`.
+    fn symbol() -> &'static str;
+    /// Number of decimals of the currency unit.
+    fn decimals() -> u8;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::Erc20Metadata;
+    use frame_support::pallet_prelude::*;
+
+    /// The balance type of this pallet.
+    pub type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config + pallet_balances::Config {
+        /// Name, symbol and decimals of the native currency.
+        type Metadata: Erc20Metadata;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(PhantomData<T>);
+
+    /// Amount that spender is allowed to transfer from the owner account.
+    #[pallet::storage]
+    #[pallet::getter(fn approvals)]
+    pub type Approvals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+}
+
+/// ERC-20 precompile of the native currency.
+///
+/// Note: state changes of its calls survive revert of the calling frame, see crate docs.
+pub struct BalancesErc20<R>(PhantomData<R>);
+
+impl<R> BalancesErc20<R>
+where
+    R: pallet_evm::Config + Config,
+    BalanceOf<R>: Into<U256> + TryFrom<U256>,
+{
+    /// Successful output of function.
+    fn output(gasometer: &Gasometer, output: Vec<u8>, logs: Vec<Log>) -> PrecompileOutput {
+        PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output,
+            logs,
+        }
+    }
+
+    fn name(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        let name = R::Metadata::name().as_bytes();
+        let output = EvmDataWriter::new().write(Bytes::from(name)).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn symbol(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        let symbol = R::Metadata::symbol().as_bytes();
+        let output = EvmDataWriter::new().write(Bytes::from(symbol)).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn decimals(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        let output = EvmDataWriter::new().write(R::Metadata::decimals()).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn total_supply(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let total_issuance = pallet_balances::Pallet::<R>::total_issuance();
+        let output = EvmDataWriter::new().write::<U256>(total_issuance.into()).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn balance_of(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let owner: Address = input.read()?;
+        let owner = R::AddressMapping::into_account_id(owner.0);
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let balance = pallet_balances::Pallet::<R>::free_balance(owner);
+        let output = EvmDataWriter::new().write::<U256>(balance.into()).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn allowance(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(2)?;
+        let owner: Address = input.read()?;
+        let spender: Address = input.read()?;
+        let owner = R::AddressMapping::into_account_id(owner.0);
+        let spender = R::AddressMapping::into_account_id(spender.0);
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let allowance = Approvals::<R>::get(owner, spender);
+        let output = EvmDataWriter::new().write::<U256>(allowance.into()).build();
+        Ok(Self::output(gasometer, output, vec![]))
+    }
+
+    fn transfer(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(2)?;
+        let to: Address = input.read()?;
+        let value = Self::read_balance(input)?;
+
+        // All gas is recorded before the transfer, it isn't reverted on failure
+        let log = Self::transfer_log(context, context.caller, to.0, value);
+        gasometer.record_log_cost(&log)?;
+        Self::do_transfer(context.caller, to.0, value, gasometer)?;
+
+        let output = EvmDataWriter::new().write(true).build();
+        Ok(Self::output(gasometer, output, vec![log]))
+    }
+
+    fn approve(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(2)?;
+        let spender: Address = input.read()?;
+        let value = Self::read_balance(input)?;
+
+        let log = log3(
+            context.address,
+            APPROVAL_TOPIC,
+            context.caller,
+            spender.0,
+            EvmDataWriter::new().write::<U256>(value.into()).build(),
+        );
+        gasometer.record_cost(RuntimeHelper::<R>::db_write_gas_cost())?;
+        gasometer.record_log_cost(&log)?;
+        Self::set_allowance(
+            R::AddressMapping::into_account_id(context.caller),
+            R::AddressMapping::into_account_id(spender.0),
+            value,
+        );
+
+        let output = EvmDataWriter::new().write(true).build();
+        Ok(Self::output(gasometer, output, vec![log]))
+    }
+
+    fn transfer_from(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+        context: &Context,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(3)?;
+        let from: Address = input.read()?;
+        let to: Address = input.read()?;
+        let value = Self::read_balance(input)?;
+
+        // Allowance is required even if the caller is owner, as in reference implementation
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let owner = R::AddressMapping::into_account_id(from.0);
+        let spender = R::AddressMapping::into_account_id(context.caller);
+        let left = Approvals::<R>::get(&owner, &spender)
+            .checked_sub(&value)
+            .ok_or_else(|| revert("Trying to spend more than allowed"))?;
+
+        // All gas is recorded before the transfer, storage changes aren't reverted on failure
+        gasometer.record_cost(RuntimeHelper::<R>::db_write_gas_cost())?;
+        let log = Self::transfer_log(context, from.0, to.0, value);
+        gasometer.record_log_cost(&log)?;
+        Self::do_transfer(from.0, to.0, value, gasometer)?;
+
+        // Allowance is spent only when transfer is done
+        Self::set_allowance(owner, spender, left);
+
+        let output = EvmDataWriter::new().write(true).build();
+        Ok(Self::output(gasometer, output, vec![log]))
+    }
+
+    /// Transfer `value` from `from` account, gas of `pallet_balances` transfer weight is charged.
+    fn do_transfer(
+        from: H160,
+        to: H160,
+        value: BalanceOf<R>,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult {
+        let weight = <R as pallet_balances::Config>::WeightInfo::transfer();
+        gasometer.record_cost(R::GasWeightMapping::weight_to_gas(weight))?;
+
+        let from = R::AddressMapping::into_account_id(from);
+        let to = R::AddressMapping::into_account_id(to);
+        <pallet_balances::Pallet<R> as Currency<R::AccountId>>::transfer(
+            &from,
+            &to,
+            value,
+            ExistenceRequirement::AllowDeath,
+        )
+        .map_err(|e| {
+            let error: &'static str = e.into();
+            let mut reason = String::from("Transfer failed: ");
+            reason.push_str(error);
+            revert(reason)
+        })
+    }
+
+    fn read_balance(input: &mut EvmDataReader) -> EvmResult<BalanceOf<R>> {
+        let value: U256 = input.read()?;
+        value
+            .try_into()
+            .map_err(|_| revert("Value is too large for balance"))
+    }
+
+    fn set_allowance(owner: R::AccountId, spender: R::AccountId, value: BalanceOf<R>) {
+        if value.is_zero() {
+            Approvals::<R>::remove(owner, spender);
+        } else {
+            Approvals::<R>::insert(owner, spender, value);
+        }
+    }
+
+    fn transfer_log(context: &Context, from: H160, to: H160, value: BalanceOf<R>) -> Log {
+        log3(
+            context.address,
+            TRANSFER_TOPIC,
+            from,
+            to,
+            EvmDataWriter::new().write::<U256>(value.into()).build(),
+        )
+    }
+}
+
+impl<R> Precompile for BalancesErc20<R>
+where
+    R: pallet_evm::Config + Config,
+    BalanceOf<R>: Into<U256> + TryFrom<U256>,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+    ) -> Result<PrecompileOutput, ExitError> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(BASE_COST)?;
+        gasometer.record_input_cost(input)?;
+        let (mut input, selector) = EvmDataReader::new_with_selector(input)?;
+
+        // Value sent to precompile would be locked on its account
+        if context.apparent_value != U256::zero() {
+            return Err(revert("Function is not payable"));
+        }
+
+        match selector {
+            // Views
+            NAME => Self::name(&mut gasometer),
+            SYMBOL => Self::symbol(&mut gasometer),
+            DECIMALS => Self::decimals(&mut gasometer),
+            TOTAL_SUPPLY => Self::total_supply(&mut gasometer),
+            BALANCE_OF => Self::balance_of(&mut input, &mut gasometer),
+            ALLOWANCE => Self::allowance(&mut input, &mut gasometer),
+            // Calls
+            TRANSFER => Self::transfer(&mut input, &mut gasometer, context),
+            APPROVE => Self::approve(&mut input, &mut gasometer, context),
+            TRANSFER_FROM => Self::transfer_from(&mut input, &mut gasometer, context),
+            _ => Err(revert("No method at given selector")),
+        }
+    }
+}
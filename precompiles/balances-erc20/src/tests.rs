@@ -0,0 +1,170 @@
+use super::*;
+use frame_support::traits::Contains;
+use mock::*;
+use pallet_evm::GasWeightMapping;
+use precompile_utils::INPUT_WORD_COST;
+
+/// Cost of `LOG3` with single word of data.
+const LOG3_COST: u64 = 375 + 3 * 375 + 8 * 32;
+
+fn call(caller: H160, input: &[u8], target_gas: Option<u64>) -> EvmResult<PrecompileOutput> {
+    let context = Context {
+        address: PRECOMPILE,
+        caller,
+        apparent_value: U256::zero(),
+    };
+    BalancesErc20::<TestRuntime>::execute(input, target_gas, &context)
+}
+
+fn input_cost(input: &[u8]) -> u64 {
+    (input.len() as u64 + 31) / 32 * INPUT_WORD_COST
+}
+
+fn transfer_gas() -> u64 {
+    <TestRuntime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+        <() as pallet_balances::WeightInfo>::transfer(),
+    )
+}
+
+fn transfer_input(to: H160, value: u128) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(TRANSFER)
+        .write(Address(to))
+        .write(U256::from(value))
+        .build()
+}
+
+fn free_balance(address: H160) -> Balance {
+    Balances::free_balance(account(address))
+}
+
+#[test]
+fn metadata_is_charged_base_cost() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = EvmDataWriter::new_with_selector(NAME).build();
+        let output = call(CHARLIE, &input, None).unwrap();
+        assert_eq!(output.cost, BASE_COST + input_cost(&input));
+        assert_eq!(
+            output.output,
+            EvmDataWriter::new().write(Bytes::from("Shiden".as_bytes())).build()
+        );
+
+        let input = EvmDataWriter::new_with_selector(DECIMALS).build();
+        let output = call(CHARLIE, &input, None).unwrap();
+        assert_eq!(output.cost, BASE_COST + input_cost(&input));
+        assert_eq!(output.output, EvmDataWriter::new().write(18u8).build());
+
+        assert_eq!(
+            call(CHARLIE, &input, Some(BASE_COST)),
+            Err(ExitError::OutOfGas)
+        );
+    })
+}
+
+#[test]
+fn transfer_is_not_filtered_by_runtime() {
+    ExternalityBuilder::build().execute_with(|| {
+        let balances_call = pallet_balances::Call::<TestRuntime>::transfer {
+            dest: account(BOB),
+            value: 100,
+        };
+        assert!(!BaseFilter::contains(&balances_call.into()));
+
+        let input = transfer_input(BOB, 100);
+        let output = call(ALICE, &input, None).unwrap();
+        assert_eq!(free_balance(ALICE), INITIAL_BALANCE - 100);
+        assert_eq!(free_balance(BOB), INITIAL_BALANCE + 100);
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&input) + LOG3_COST + transfer_gas()
+        );
+        assert_eq!(
+            output.logs,
+            vec![log3(
+                PRECOMPILE,
+                TRANSFER_TOPIC,
+                ALICE,
+                BOB,
+                EvmDataWriter::new().write(U256::from(100)).build(),
+            )]
+        );
+    })
+}
+
+#[test]
+fn transfer_failure_reverts_with_reason() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_eq!(
+            call(CHARLIE, &transfer_input(BOB, 100), None),
+            Err(ExitError::Other("Transfer failed: InsufficientBalance".into()))
+        );
+
+        // Nothing is transferred without gas for the transfer weight
+        let input = transfer_input(BOB, 100);
+        let target_gas = BASE_COST + input_cost(&input) + LOG3_COST;
+        assert_eq!(
+            call(ALICE, &input, Some(target_gas)),
+            Err(ExitError::OutOfGas)
+        );
+        assert_eq!(free_balance(ALICE), INITIAL_BALANCE);
+    })
+}
+
+#[test]
+fn transfer_from_spends_allowance() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = EvmDataWriter::new_with_selector(APPROVE)
+            .write(Address(CHARLIE))
+            .write(U256::from(150))
+            .build();
+        call(ALICE, &input, None).unwrap();
+        assert_eq!(Erc20::approvals(account(ALICE), account(CHARLIE)), 150);
+
+        let transfer_from = |value: u128| {
+            let input = EvmDataWriter::new_with_selector(TRANSFER_FROM)
+                .write(Address(ALICE))
+                .write(Address(BOB))
+                .write(U256::from(value))
+                .build();
+            call(CHARLIE, &input, None)
+        };
+        assert_eq!(
+            transfer_from(200),
+            Err(ExitError::Other("Trying to spend more than allowed".into()))
+        );
+
+        transfer_from(100).unwrap();
+        assert_eq!(free_balance(ALICE), INITIAL_BALANCE - 100);
+        assert_eq!(free_balance(BOB), INITIAL_BALANCE + 100);
+        assert_eq!(Erc20::approvals(account(ALICE), account(CHARLIE)), 50);
+    })
+}
+
+#[test]
+fn transfer_from_own_account_needs_allowance() {
+    ExternalityBuilder::build().execute_with(|| {
+        let transfer_from = || {
+            let input = EvmDataWriter::new_with_selector(TRANSFER_FROM)
+                .write(Address(ALICE))
+                .write(Address(BOB))
+                .write(U256::from(100))
+                .build();
+            call(ALICE, &input, None)
+        };
+        assert_eq!(
+            transfer_from(),
+            Err(ExitError::Other("Trying to spend more than allowed".into()))
+        );
+        assert_eq!(free_balance(ALICE), INITIAL_BALANCE);
+
+        let input = EvmDataWriter::new_with_selector(APPROVE)
+            .write(Address(ALICE))
+            .write(U256::from(100))
+            .build();
+        call(ALICE, &input, None).unwrap();
+
+        transfer_from().unwrap();
+        assert_eq!(free_balance(ALICE), INITIAL_BALANCE - 100);
+        assert_eq!(free_balance(BOB), INITIAL_BALANCE + 100);
+        assert_eq!(Erc20::approvals(account(ALICE), account(ALICE)), 0);
+    })
+}
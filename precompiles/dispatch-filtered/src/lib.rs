@@ -0,0 +1,69 @@
+//! Dispatch of SCALE-encoded runtime calls, limited by the call filter.
+//!
+//! It works like `pallet_evm_precompile_dispatch::Dispatch`, but only calls allowed by
+//! `F: Contains<Call>` are dispatched. The filter is also added to the dispatch origin, so the
+//! calls nested into allowed ones (e.g. `utility.batch`) are filtered as well.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, DecodeLimit};
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::{
+    dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    traits::{Contains, OriginTrait},
+    weights::{DispatchClass, Pays},
+};
+use pallet_evm::{AddressMapping, Precompile};
+use precompile_utils::{revert, Gasometer, RuntimeHelper};
+use sp_std::marker::PhantomData;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub struct FilteredDispatch<R, F>(PhantomData<(R, F)>);
+
+impl<R, F> Precompile for FilteredDispatch<R, F>
+where
+    R: pallet_evm::Config,
+    R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + Decode,
+    <R::Call as Dispatchable>::Origin:
+        From<Option<R::AccountId>> + OriginTrait<Call = R::Call>,
+    F: Contains<R::Call> + 'static,
+{
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        context: &Context,
+    ) -> Result<PrecompileOutput, ExitError> {
+        let mut gasometer = Gasometer::new(target_gas);
+
+        // Decoding is paid upfront, input length is up to the caller
+        gasometer.record_input_cost(input)?;
+
+        // Nesting depth is limited as for extrinsics, trailing bytes are not allowed
+        let call = R::Call::decode_all_with_depth_limit(sp_api::MAX_EXTRINSIC_DEPTH, input)
+            .map_err(|_| revert("Failed to decode call"))?;
+        if !F::contains(&call) {
+            return Err(revert("Call is not allowed to dispatch from EVM"));
+        }
+
+        let info = call.get_dispatch_info();
+        if info.pays_fee != Pays::Yes || info.class != DispatchClass::Normal {
+            return Err(revert("Only normal class calls paying fee could be dispatched"));
+        }
+
+        let mut origin: <R::Call as Dispatchable>::Origin =
+            Some(R::AddressMapping::into_account_id(context.caller)).into();
+        origin.add_filter(F::contains);
+        RuntimeHelper::<R>::try_dispatch_origin(origin, call, &mut gasometer)?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Stopped,
+            cost: gasometer.used_gas(),
+            output: Default::default(),
+            logs: Default::default(),
+        })
+    }
+}
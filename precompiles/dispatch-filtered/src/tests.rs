@@ -0,0 +1,140 @@
+use super::*;
+use codec::Encode;
+use mock::*;
+use pallet_evm::GasWeightMapping;
+use precompile_utils::{EvmResult, INPUT_WORD_COST};
+use sp_core::{H160, U256};
+
+type Dispatch = FilteredDispatch<TestRuntime, TestFilter>;
+
+fn call(caller: H160, input: &[u8], target_gas: Option<u64>) -> EvmResult<PrecompileOutput> {
+    let context = Context {
+        address: PRECOMPILE,
+        caller,
+        apparent_value: U256::zero(),
+    };
+    Dispatch::execute(input, target_gas, &context)
+}
+
+fn input_cost(input: &[u8]) -> u64 {
+    (input.len() as u64 + 31) / 32 * INPUT_WORD_COST
+}
+
+fn weight_to_gas(call: &Call) -> u64 {
+    <TestRuntime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(
+        call.get_dispatch_info().weight,
+    )
+}
+
+fn transfer(value: Balance) -> Call {
+    Call::Balances(pallet_balances::Call::transfer {
+        dest: account(BOB),
+        value,
+    })
+}
+
+#[test]
+fn allowed_call_is_dispatched() {
+    ExternalityBuilder::build().execute_with(|| {
+        let transfer = transfer(100);
+        let input = transfer.encode();
+        let output = call(ALICE, &input, None).unwrap();
+
+        assert_eq!(output.exit_status, ExitSucceed::Stopped);
+        assert_eq!(output.cost, input_cost(&input) + weight_to_gas(&transfer));
+        assert_eq!(Balances::free_balance(account(BOB)), 100);
+    })
+}
+
+#[test]
+fn disallowed_call_is_reverted() {
+    ExternalityBuilder::build().execute_with(|| {
+        let transfer = Call::Balances(pallet_balances::Call::transfer_keep_alive {
+            dest: account(BOB),
+            value: 100,
+        });
+        assert_eq!(
+            call(ALICE, &transfer.encode(), None),
+            Err(ExitError::Other("Call is not allowed to dispatch from EVM".into()))
+        );
+        assert_eq!(Balances::free_balance(account(BOB)), 0);
+
+        assert_eq!(
+            call(ALICE, &[0xff; 4], None),
+            Err(ExitError::Other("Failed to decode call".into()))
+        );
+    })
+}
+
+#[test]
+fn call_with_trailing_bytes_is_reverted() {
+    ExternalityBuilder::build().execute_with(|| {
+        let mut input = transfer(100).encode();
+        input.push(0);
+        assert_eq!(
+            call(ALICE, &input, None),
+            Err(ExitError::Other("Failed to decode call".into()))
+        );
+        assert_eq!(Balances::free_balance(account(BOB)), 0);
+    })
+}
+
+#[test]
+fn too_deeply_nested_call_is_reverted() {
+    ExternalityBuilder::build().execute_with(|| {
+        let nested = |depth| {
+            (0..depth).fold(transfer(100), |call, _| {
+                Call::Utility(pallet_utility::Call::batch { calls: vec![call] })
+            })
+        };
+
+        assert_eq!(
+            call(ALICE, &nested(sp_api::MAX_EXTRINSIC_DEPTH + 1).encode(), None),
+            Err(ExitError::Other("Failed to decode call".into()))
+        );
+        assert_eq!(Balances::free_balance(account(BOB)), 0);
+
+        call(ALICE, &nested(4).encode(), None).unwrap();
+        assert_eq!(Balances::free_balance(account(BOB)), 100);
+    })
+}
+
+#[test]
+fn disallowed_call_nested_in_batch_is_filtered() {
+    ExternalityBuilder::build().execute_with(|| {
+        let batch = Call::Utility(pallet_utility::Call::batch {
+            calls: vec![
+                transfer(100),
+                Call::Balances(pallet_balances::Call::transfer_keep_alive {
+                    dest: account(BOB),
+                    value: 200,
+                }),
+            ],
+        });
+
+        // Batch itself is allowed, it's interrupted by the filtered call
+        call(ALICE, &batch.encode(), None).unwrap();
+        assert_eq!(Balances::free_balance(account(BOB)), 100);
+    })
+}
+
+#[test]
+fn not_enough_gas_for_call_weight() {
+    ExternalityBuilder::build().execute_with(|| {
+        let transfer = transfer(100);
+        let input = transfer.encode();
+        let target_gas = input_cost(&input) + weight_to_gas(&transfer) - 1;
+
+        assert_eq!(
+            call(ALICE, &input, Some(target_gas)),
+            Err(ExitError::OutOfGas)
+        );
+        assert_eq!(Balances::free_balance(account(BOB)), 0);
+
+        // Input cost is charged upfront
+        assert_eq!(
+            call(ALICE, &input, Some(input_cost(&input) - 1)),
+            Err(ExitError::OutOfGas)
+        );
+    })
+}
@@ -3,19 +3,90 @@
 pragma solidity >=0.7.0;
 
 interface Staking {
+    /*
+     * @dev Session keys of collator are set, keys_hash is keccak256 of SCALE-encoded keys.
+     */
+    event KeysSet(address indexed collator, bytes32 indexed keys_hash);
+
+    /*
+     * @dev Session keys of collator are removed.
+     */
+    event KeysPurged(address indexed collator);
+
+    /*
+     * @dev Collator is registered as collation candidate.
+     */
+    event CandidateRegistered(address indexed collator);
+
+    /*
+     * @dev Collator has left collation candidacy.
+     */
+    event CandidateLeft(address indexed collator);
+
+    /*
+     * @dev Collation staking deposit of collator is unlocked.
+     */
+    event BondWithdrawn(address indexed collator);
+
+    /*
+     * @dev Accounts of collation candidates.
+     */
+    function candidates() external view returns (bytes32[] memory);
+
+    /*
+     * @dev Accounts of invulnerable collators.
+     */
+    function invulnerables() external view returns (bytes32[] memory);
+
+    /*
+     * @dev Collation staking deposit of candidates.
+     */
+    function candidacy_bond() external view returns (uint256);
+
+    /*
+     * @dev Desired number of candidates.
+     */
+    function desired_candidates() external view returns (uint32);
+
+    /*
+     * @dev Check that collator address is a collation candidate.
+     */
+    function is_candidate(address collator) external view returns (bool);
+
+    /*
+     * @dev SCALE-encoded session keys of collator for the next session, empty if not set.
+     */
+    function next_keys(address collator) external view returns (bytes memory);
+
     /*
      * @dev Set session keys of function caller.
+     * @note Emits KeysSet event.
      */
     function set_keys(bytes calldata keys) external;
 
     /*
      * @dev Removes any session keys of the function caller.
+     * @note Emits KeysPurged event.
      */
     function purge_keys() external;
 
     /*
      * @dev Register function caller as collation candidate.
      * @note Collation staking deposit will be locked.
+     * @note Emits CandidateRegistered event.
      */
     function register_as_candidate() external;
+
+    /*
+     * @dev Leave collation candidacy of function caller.
+     * @note Collation staking deposit stays locked for cooldown sessions.
+     * @note Emits CandidateLeft event.
+     */
+    function leave_intent() external;
+
+    /*
+     * @dev Unlock collation staking deposit of function caller when cooldown is elapsed.
+     * @note Emits BondWithdrawn event.
+     */
+    function withdraw_bond() external;
 }
@@ -2,104 +2,245 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use codec::Decode;
-use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
-use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
-use pallet_evm::{AddressMapping, GasWeightMapping, Precompile};
-use sp_std::{marker::PhantomData, vec::Vec};
+use codec::{DecodeAll, Encode};
+use evm::{backend::Log, executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use frame_support::{
+    dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    traits::Currency,
+};
+use pallet_evm::{AddressMapping, Precompile};
+use precompile_utils::{
+    keccak256, log2, revert, selector, Address, Bytes, EvmDataReader, EvmDataWriter, EvmResult,
+    Gasometer, RuntimeHelper,
+};
+use sp_core::{H256, U256};
+use sp_runtime::traits::Convert;
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+// ======= Staking.sol:Staking =======
+const SET_KEYS: u32 = selector!("set_keys(bytes)");
+const PURGE_KEYS: u32 = selector!("purge_keys()");
+const REGISTER_AS_CANDIDATE: u32 = selector!("register_as_candidate()");
+const LEAVE_INTENT: u32 = selector!("leave_intent()");
+const WITHDRAW_BOND: u32 = selector!("withdraw_bond()");
+const CANDIDATES: u32 = selector!("candidates()");
+const INVULNERABLES: u32 = selector!("invulnerables()");
+const CANDIDACY_BOND: u32 = selector!("candidacy_bond()");
+const DESIRED_CANDIDATES: u32 = selector!("desired_candidates()");
+const IS_CANDIDATE: u32 = selector!("is_candidate(address)");
+const NEXT_KEYS: u32 = selector!("next_keys(address)");
+
+const KEYS_SET: [u8; 32] = keccak256!("KeysSet(address,bytes32)");
+const KEYS_PURGED: [u8; 32] = keccak256!("KeysPurged(address)");
+const CANDIDATE_REGISTERED: [u8; 32] = keccak256!("CandidateRegistered(address)");
+const CANDIDATE_LEFT: [u8; 32] = keccak256!("CandidateLeft(address)");
+const BOND_WITHDRAWN: [u8; 32] = keccak256!("BondWithdrawn(address)");
+
+/// Gas cost of precompile call, dispatched call weight, storage reads and list output are
+/// charged on top.
+pub const BASE_COST: u64 = 500;
+
+/// Balance of collator candidacy bond.
+pub type BalanceOf<R> = <<R as pallet_collator_selection::Config>::Currency as Currency<
+    <R as frame_system::Config>::AccountId,
+>>::Balance;
 
 pub struct Staking<R>(PhantomData<R>);
 
 impl<R> Staking<R>
 where
-    R: pallet_session::Config + pallet_collator_selection::Config,
-    R::Call: From<pallet_session::Call<R>> + From<pallet_collator_selection::Call<R>>,
+    R: pallet_evm::Config
+        + pallet_session::Config
+        + pallet_collator_selection::Config
+        + pallet_collator_exit::Config,
+    R::Call: From<pallet_session::Call<R>>
+        + From<pallet_collator_selection::Call<R>>
+        + From<pallet_collator_exit::Call<R>>
+        + Dispatchable<PostInfo = PostDispatchInfo>
+        + GetDispatchInfo,
+    <R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+    R::AccountId: AsRef<[u8; 32]>,
+    BalanceOf<R>: Into<U256>,
 {
-    fn set_keys(keys: Vec<u8>) -> Result<R::Call, ExitError> {
-        let keys = <R as pallet_session::Config>::Keys::decode(&mut &keys[..])
-            .map_err(|_| ExitError::Other("Unable to decode session keys".into()))?;
-        Ok(pallet_session::Call::<R>::set_keys {
+    /// Successful output of view function.
+    fn output(gasometer: &Gasometer, output: Vec<u8>) -> PrecompileOutput {
+        PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output,
+            logs: Default::default(),
+        }
+    }
+
+    fn candidates(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let candidates: Vec<H256> = pallet_collator_selection::Pallet::<R>::candidates()
+            .into_iter()
+            .map(|candidate| H256::from(*candidate.who.as_ref()))
+            .collect();
+        // List length is up to the runtime, so it's charged by output words
+        let output = EvmDataWriter::new().write(candidates).build();
+        gasometer.record_output_cost(&output)?;
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn invulnerables(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let invulnerables: Vec<H256> = pallet_collator_selection::Pallet::<R>::invulnerables()
+            .into_iter()
+            .map(|who| H256::from(*who.as_ref()))
+            .collect();
+        // List length is up to the runtime, so it's charged by output words
+        let output = EvmDataWriter::new().write(invulnerables).build();
+        gasometer.record_output_cost(&output)?;
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn candidacy_bond(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let bond: U256 = pallet_collator_selection::Pallet::<R>::candidacy_bond().into();
+        let output = EvmDataWriter::new().write(bond).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn desired_candidates(gasometer: &mut Gasometer) -> EvmResult<PrecompileOutput> {
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let desired = pallet_collator_selection::Pallet::<R>::desired_candidates();
+        let output = EvmDataWriter::new().write(desired).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn is_candidate(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let collator: Address = input.read()?;
+        let collator = R::AddressMapping::into_account_id(collator.0);
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let is_candidate = pallet_collator_selection::Pallet::<R>::candidates()
+            .iter()
+            .any(|candidate| candidate.who == collator);
+        let output = EvmDataWriter::new().write(is_candidate).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn next_keys(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(1)?;
+        let collator: Address = input.read()?;
+        let collator = R::AddressMapping::into_account_id(collator.0);
+
+        gasometer.record_cost(RuntimeHelper::<R>::db_read_gas_cost())?;
+        let keys = <R as pallet_session::Config>::ValidatorIdOf::convert(collator)
+            .and_then(|validator| pallet_session::NextKeys::<R>::get(validator))
+            .map(|keys| keys.encode())
+            .unwrap_or_default();
+        let output = EvmDataWriter::new().write(Bytes(keys)).build();
+        Ok(Self::output(gasometer, output))
+    }
+
+    fn set_keys(input: &mut EvmDataReader, context: &Context) -> EvmResult<(R::Call, Log)> {
+        input.expect_arguments(1)?;
+        let keys: Bytes = input.read()?;
+        let keys = <R as pallet_session::Config>::Keys::decode_all(&keys.0)
+            .map_err(|_| revert("Unable to decode session keys"))?;
+        let keys_hash = sp_io::hashing::keccak_256(&keys.encode());
+
+        let call = pallet_session::Call::<R>::set_keys {
             keys,
             proof: Default::default(),
-        }
-        .into())
+        };
+        let log = log2(context.address, KEYS_SET, context.caller, keys_hash, vec![]);
+        Ok((call.into(), log))
     }
 
-    fn purge_keys() -> R::Call {
-        pallet_session::Call::<R>::purge_keys {}.into()
+    fn purge_keys(context: &Context) -> (R::Call, Log) {
+        let call = pallet_session::Call::<R>::purge_keys {};
+        (call.into(), Self::caller_log(context, KEYS_PURGED))
     }
 
-    fn register_as_candidate() -> R::Call {
-        pallet_collator_selection::Call::<R>::register_as_candidate {}.into()
+    fn register_as_candidate(context: &Context) -> (R::Call, Log) {
+        let call = pallet_collator_selection::Call::<R>::register_as_candidate {};
+        (call.into(), Self::caller_log(context, CANDIDATE_REGISTERED))
+    }
+
+    fn leave_intent(context: &Context) -> (R::Call, Log) {
+        let call = pallet_collator_exit::Call::<R>::leave_intent {};
+        (call.into(), Self::caller_log(context, CANDIDATE_LEFT))
+    }
+
+    fn withdraw_bond(context: &Context) -> (R::Call, Log) {
+        let call = pallet_collator_exit::Call::<R>::withdraw_bond {};
+        (call.into(), Self::caller_log(context, BOND_WITHDRAWN))
+    }
+
+    /// Log of the event that has indexed caller as the only argument.
+    fn caller_log(context: &Context, topic: [u8; 32]) -> Log {
+        log2(context.address, topic, context.caller, vec![])
     }
 }
 
 impl<R> Precompile for Staking<R>
 where
-    R: pallet_evm::Config + pallet_session::Config + pallet_collator_selection::Config,
+    R: pallet_evm::Config
+        + pallet_session::Config
+        + pallet_collator_selection::Config
+        + pallet_collator_exit::Config,
     R::Call: From<pallet_session::Call<R>>
         + From<pallet_collator_selection::Call<R>>
+        + From<pallet_collator_exit::Call<R>>
         + Dispatchable<PostInfo = PostDispatchInfo>
         + GetDispatchInfo,
     <R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+    R::AccountId: AsRef<[u8; 32]>,
+    BalanceOf<R>: Into<U256>,
 {
     fn execute(
         input: &[u8],
         target_gas: Option<u64>,
         context: &Context,
     ) -> Result<PrecompileOutput, ExitError> {
-        const SELECTOR_SIZE_BYTES: usize = 4;
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(BASE_COST)?;
+        gasometer.record_input_cost(input)?;
+        let (mut input, selector) = EvmDataReader::new_with_selector(input)?;
 
-        if input.len() < SELECTOR_SIZE_BYTES {
-            return Err(ExitError::Other("input length less than 4 bytes".into()));
-        }
-
-        // ======= Staking.sol:Staking =======
-        // Function signatures:
-        // bcb24ddc: set_keys(bytes)
-        // 321c9b7a: purge_keys()
-        // d09b6ba5: register_as_candidate()
-        let call = match input[0..SELECTOR_SIZE_BYTES] {
-            [0xbc, 0xb2, 0x4d, 0xdc] => {
-                if input.len() < SELECTOR_SIZE_BYTES + 32 * 2 {
-                    return Err(ExitError::Other("input length less than 36 bytes".into()));
-                }
-                // Low level argument parsing
-                let len_offset = SELECTOR_SIZE_BYTES + 32;
-                let keys_offset = len_offset + 32;
-                let keys_len = sp_core::U256::from_big_endian(&input[len_offset..keys_offset]);
-                let keys = input[keys_offset..(keys_offset + keys_len.as_usize())].to_vec();
-                Self::set_keys(keys)?
-            }
-            [0x32, 0x1c, 0x9b, 0x7a] => Self::purge_keys(),
-            [0xd0, 0x9b, 0x6b, 0xa5] => Self::register_as_candidate(),
-            _ => {
-                return Err(ExitError::Other(
-                    "No method at selector given selector".into(),
-                ))
-            }
+        let (call, log) = match selector {
+            // Views
+            CANDIDATES => return Self::candidates(&mut gasometer),
+            INVULNERABLES => return Self::invulnerables(&mut gasometer),
+            CANDIDACY_BOND => return Self::candidacy_bond(&mut gasometer),
+            DESIRED_CANDIDATES => return Self::desired_candidates(&mut gasometer),
+            IS_CANDIDATE => return Self::is_candidate(&mut input, &mut gasometer),
+            NEXT_KEYS => return Self::next_keys(&mut input, &mut gasometer),
+            // Calls
+            SET_KEYS => Self::set_keys(&mut input, context)?,
+            PURGE_KEYS => Self::purge_keys(context),
+            REGISTER_AS_CANDIDATE => Self::register_as_candidate(context),
+            LEAVE_INTENT => Self::leave_intent(context),
+            WITHDRAW_BOND => Self::withdraw_bond(context),
+            _ => return Err(revert("No method at given selector")),
         };
 
-        let info = call.get_dispatch_info();
-        if let Some(gas_limit) = target_gas {
-            let required_gas = R::GasWeightMapping::weight_to_gas(info.weight);
-            if required_gas > gas_limit {
-                return Err(ExitError::OutOfGas);
-            }
-        }
-
+        // Dispatched call isn't reverted on error, so the log is paid upfront
+        gasometer.record_log_cost(&log)?;
         let origin = R::AddressMapping::into_account_id(context.caller);
-        let post_info = call
-            .dispatch(Some(origin).into())
-            .map_err(|_| ExitError::Other("Method call via EVM failed".into()))?;
+        RuntimeHelper::<R>::try_dispatch(Some(origin), call, &mut gasometer)?;
 
-        let gas_used =
-            R::GasWeightMapping::weight_to_gas(post_info.actual_weight.unwrap_or(info.weight));
         Ok(PrecompileOutput {
             exit_status: ExitSucceed::Stopped,
-            cost: gas_used,
+            cost: gasometer.used_gas(),
             output: Default::default(),
-            logs: Default::default(),
+            logs: vec![log],
         })
     }
 }
@@ -0,0 +1,226 @@
+use frame_support::{construct_runtime, parameter_types, PalletId};
+use pallet_evm::AddressMapping;
+use sp_core::{H160, H256, U256};
+
+use sp_io::TestExternalities;
+use sp_runtime::{
+    testing::{Header, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32,
+};
+
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+pub(crate) const INITIAL_BALANCE: Balance = 1_000;
+pub(crate) const CANDIDACY_BOND: Balance = 10;
+pub(crate) const MAX_CANDIDATES: u32 = 20;
+
+/// Address of the staking precompile.
+pub(crate) const PRECOMPILE: H160 = H160([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x50, 0x00,
+]);
+/// Invulnerable collator of genesis validator set.
+pub(crate) const INVULNERABLE: H160 = H160([1; 20]);
+/// Address with session keys that isn't a candidate at genesis.
+pub(crate) const COLLATOR: H160 = H160([2; 20]);
+/// Address without session keys.
+pub(crate) const NOBODY: H160 = H160([3; 20]);
+
+/// Account of EVM address.
+pub(crate) fn account(address: H160) -> AccountId {
+    <TestRuntime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+construct_runtime!(
+    pub enum TestRuntime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+        EVM: pallet_evm::{Pallet, Call, Storage, Event<T>},
+        CollatorSelection: pallet_collator_selection::{Pallet, Call, Storage, Event<T>, Config<T>},
+        Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+        CollatorExit: pallet_collator_exit::{Pallet, Call, Storage, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type Origin = Origin;
+    type Index = u64;
+    type Call = Call;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type DbWeight = ();
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const MaxLocks: u32 = 4;
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MinimumPeriod: u64 = 5;
+}
+
+impl pallet_timestamp::Config for TestRuntime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub BlockGasLimit: U256 = U256::from(u32::max_value());
+}
+
+impl pallet_evm::Config for TestRuntime {
+    type FeeCalculator = ();
+    type GasWeightMapping = ();
+    type BlockHashMapping = pallet_evm::SubstrateBlockHashMapping<Self>;
+    type CallOrigin = pallet_evm::EnsureAddressRoot<AccountId>;
+    type WithdrawOrigin = pallet_evm::EnsureAddressNever<AccountId>;
+    type AddressMapping = pallet_evm::HashedAddressMapping<BlakeTwo256>;
+    type Currency = Balances;
+    type Event = Event;
+    type Runner = pallet_evm::runner::stack::Runner<Self>;
+    type Precompiles = ();
+    type ChainId = ();
+    type OnChargeTransaction = ();
+    type BlockGasLimit = BlockGasLimit;
+    type FindAuthor = ();
+}
+
+parameter_types! {
+    pub const SessionPeriod: BlockNumber = 10;
+    pub const SessionOffset: BlockNumber = 0;
+}
+
+impl pallet_session::Config for TestRuntime {
+    type Event = Event;
+    type ValidatorId = <Self as frame_system::Config>::AccountId;
+    type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+    type ShouldEndSession = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type NextSessionRotation = pallet_session::PeriodicSessions<SessionPeriod, SessionOffset>;
+    type SessionManager = CollatorSelection;
+    type SessionHandler = pallet_session::TestSessionHandler;
+    type Keys = UintAuthorityId;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const PotId: PalletId = PalletId(*b"PotStake");
+    pub const MaxCandidates: u32 = MAX_CANDIDATES;
+    pub const MinCandidates: u32 = 0;
+    pub const MaxInvulnerables: u32 = 20;
+    // Candidates shouldn't be kicked during tests
+    pub const KickThreshold: BlockNumber = 1_000;
+}
+
+impl pallet_collator_selection::Config for TestRuntime {
+    type Event = Event;
+    type Currency = Balances;
+    type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+    type PotId = PotId;
+    type MaxCandidates = MaxCandidates;
+    type MinCandidates = MinCandidates;
+    type MaxInvulnerables = MaxInvulnerables;
+    type KickThreshold = KickThreshold;
+    type ValidatorId = <Self as frame_system::Config>::AccountId;
+    type ValidatorIdOf = pallet_collator_selection::IdentityCollator;
+    type ValidatorRegistration = Session;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const Cooldown: u32 = 2;
+}
+
+impl pallet_collator_exit::Config for TestRuntime {
+    type Event = Event;
+    type Cooldown = Cooldown;
+}
+
+pub struct ExternalityBuilder;
+
+impl ExternalityBuilder {
+    pub fn build() -> TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<TestRuntime>()
+            .unwrap();
+
+        pallet_balances::GenesisConfig::<TestRuntime> {
+            balances: [INVULNERABLE, COLLATOR, NOBODY]
+                .iter()
+                .map(|address| (account(*address), INITIAL_BALANCE))
+                .collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        pallet_collator_selection::GenesisConfig::<TestRuntime> {
+            invulnerables: vec![account(INVULNERABLE)],
+            candidacy_bond: CANDIDACY_BOND,
+            desired_candidates: 2,
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        pallet_session::GenesisConfig::<TestRuntime> {
+            keys: [INVULNERABLE, COLLATOR]
+                .iter()
+                .enumerate()
+                .map(|(index, address)| {
+                    let who = account(*address);
+                    (who.clone(), who, UintAuthorityId(index as u64))
+                })
+                .collect(),
+        }
+        .assimilate_storage(&mut storage)
+        .ok();
+
+        let mut ext = TestExternalities::from(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+}
@@ -0,0 +1,237 @@
+use super::*;
+use mock::*;
+use pallet_collator_selection::WeightInfo;
+use pallet_evm::GasWeightMapping;
+use precompile_utils::INPUT_WORD_COST;
+use sp_core::H160;
+use sp_runtime::testing::UintAuthorityId;
+
+/// Cost of `LOG2` without data.
+const LOG2_COST: u64 = 375 + 2 * 375;
+
+fn call(caller: H160, input: &[u8], target_gas: Option<u64>) -> EvmResult<PrecompileOutput> {
+    let context = Context {
+        address: PRECOMPILE,
+        caller,
+        apparent_value: U256::zero(),
+    };
+    Staking::<TestRuntime>::execute(input, target_gas, &context)
+}
+
+fn input(selector: u32) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(selector).build()
+}
+
+fn input_cost(input: &[u8]) -> u64 {
+    (input.len() as u64 + 31) / 32 * INPUT_WORD_COST
+}
+
+fn weight_to_gas(weight: u64) -> u64 {
+    <TestRuntime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
+fn is_candidate(address: H160) -> bool {
+    CollatorSelection::candidates()
+        .iter()
+        .any(|candidate| candidate.who == account(address))
+}
+
+#[test]
+fn view_is_charged_base_and_input_cost() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = input(DESIRED_CANDIDATES);
+        let output = call(NOBODY, &input, None).unwrap();
+
+        // Storage reads are free with unit `DbWeight`
+        assert_eq!(output.cost, BASE_COST + input_cost(&input));
+        assert_eq!(output.output, EvmDataWriter::new().write(2u32).build());
+
+        // Long input costs more
+        let mut long_input = input;
+        long_input.extend_from_slice(&[0u8; 64]);
+        let output = call(NOBODY, &long_input, None).unwrap();
+        assert_eq!(output.cost, BASE_COST + 3 * INPUT_WORD_COST);
+    })
+}
+
+#[test]
+fn list_view_is_charged_by_output_words() {
+    ExternalityBuilder::build().execute_with(|| {
+        let list = |accounts: Vec<H160>| {
+            let accounts: Vec<H256> = accounts
+                .into_iter()
+                .map(|address| H256::from(<[u8; 32]>::from(account(address))))
+                .collect();
+            EvmDataWriter::new().write(accounts).build()
+        };
+
+        // Offset and length words of empty list
+        let candidates = input(CANDIDATES);
+        let output = call(NOBODY, &candidates, None).unwrap();
+        assert_eq!(output.output, list(vec![]));
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&candidates) + 2 * INPUT_WORD_COST
+        );
+
+        // and one word of each entry
+        call(COLLATOR, &input(REGISTER_AS_CANDIDATE), None).unwrap();
+        let output = call(NOBODY, &candidates, None).unwrap();
+        assert_eq!(output.output, list(vec![COLLATOR]));
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&candidates) + 3 * INPUT_WORD_COST
+        );
+
+        let invulnerables = input(INVULNERABLES);
+        let output = call(NOBODY, &invulnerables, None).unwrap();
+        assert_eq!(output.output, list(vec![INVULNERABLE]));
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&invulnerables) + 3 * INPUT_WORD_COST
+        );
+    })
+}
+
+#[test]
+fn not_enough_gas_for_base_cost() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = input(DESIRED_CANDIDATES);
+        assert_eq!(
+            call(NOBODY, &input, Some(BASE_COST)),
+            Err(ExitError::OutOfGas)
+        );
+    })
+}
+
+#[test]
+fn register_as_candidate_is_charged_actual_weight() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = input(REGISTER_AS_CANDIDATE);
+        let output = call(COLLATOR, &input, None).unwrap();
+        assert!(is_candidate(COLLATOR));
+
+        let actual_weight = <() as WeightInfo>::register_as_candidate(1);
+        assert!(actual_weight < <() as WeightInfo>::register_as_candidate(MAX_CANDIDATES));
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&input) + weight_to_gas(actual_weight) + LOG2_COST
+        );
+        assert_eq!(
+            output.logs,
+            vec![log2(PRECOMPILE, CANDIDATE_REGISTERED, COLLATOR, vec![])]
+        );
+    })
+}
+
+#[test]
+fn leave_intent_is_refunded() {
+    ExternalityBuilder::build().execute_with(|| {
+        call(COLLATOR, &input(REGISTER_AS_CANDIDATE), None).unwrap();
+
+        // Only the weight of removal from single candidate is charged
+        let input = input(LEAVE_INTENT);
+        let output = call(COLLATOR, &input, None).unwrap();
+        assert!(!is_candidate(COLLATOR));
+
+        let actual_weight = <() as WeightInfo>::leave_intent(0);
+        assert!(actual_weight < <() as WeightInfo>::leave_intent(MAX_CANDIDATES));
+        assert_eq!(
+            output.cost,
+            BASE_COST + input_cost(&input) + weight_to_gas(actual_weight) + LOG2_COST
+        );
+        assert_eq!(
+            output.logs,
+            vec![log2(PRECOMPILE, CANDIDATE_LEFT, COLLATOR, vec![])]
+        );
+    })
+}
+
+#[test]
+fn not_enough_gas_for_call_weight() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = input(REGISTER_AS_CANDIDATE);
+        let target_gas = BASE_COST + input_cost(&input) + LOG2_COST;
+        assert_eq!(
+            call(COLLATOR, &input, Some(target_gas)),
+            Err(ExitError::OutOfGas)
+        );
+        assert!(!is_candidate(COLLATOR));
+    })
+}
+
+#[test]
+fn not_enough_gas_for_log() {
+    ExternalityBuilder::build().execute_with(|| {
+        let input = input(REGISTER_AS_CANDIDATE);
+        let weight = <() as WeightInfo>::register_as_candidate(MAX_CANDIDATES);
+        let target_gas = BASE_COST + input_cost(&input) + weight_to_gas(weight);
+        assert_eq!(
+            call(COLLATOR, &input, Some(target_gas)),
+            Err(ExitError::OutOfGas)
+        );
+        // Log is charged before dispatch, so the call has no effect
+        assert!(!is_candidate(COLLATOR));
+    })
+}
+
+#[test]
+fn set_keys_emits_keys_hash() {
+    ExternalityBuilder::build().execute_with(|| {
+        let keys = UintAuthorityId(3).encode();
+        let input = EvmDataWriter::new_with_selector(SET_KEYS)
+            .write(Bytes(keys))
+            .build();
+        let output = call(NOBODY, &input, None).unwrap();
+
+        assert_eq!(
+            pallet_session::NextKeys::<TestRuntime>::get(account(NOBODY)),
+            Some(UintAuthorityId(3))
+        );
+        assert_eq!(
+            output.logs,
+            vec![log2(
+                PRECOMPILE,
+                KEYS_SET,
+                NOBODY,
+                sp_io::hashing::keccak_256(&UintAuthorityId(3).encode()),
+                vec![]
+            )]
+        );
+    })
+}
+
+#[test]
+fn set_keys_with_trailing_bytes_is_reverted() {
+    ExternalityBuilder::build().execute_with(|| {
+        let mut keys = UintAuthorityId(3).encode();
+        keys.push(0);
+        let input = EvmDataWriter::new_with_selector(SET_KEYS)
+            .write(Bytes(keys))
+            .build();
+
+        assert_eq!(
+            call(NOBODY, &input, None),
+            Err(ExitError::Other("Unable to decode session keys".into()))
+        );
+        assert_eq!(pallet_session::NextKeys::<TestRuntime>::get(account(NOBODY)), None);
+    })
+}
+
+#[test]
+fn dispatch_error_reverts_with_error_name() {
+    ExternalityBuilder::build().execute_with(|| {
+        assert_eq!(
+            call(NOBODY, &input(WITHDRAW_BOND), None),
+            Err(ExitError::Other("Dispatched call failed: NotLeaving".into()))
+        );
+        assert_eq!(
+            call(NOBODY, &input(LEAVE_INTENT), None),
+            Err(ExitError::Other("Dispatched call failed: NotCandidate".into()))
+        );
+        assert_eq!(
+            call(NOBODY, &[0u8; 4], None),
+            Err(ExitError::Other("No method at given selector".into()))
+        );
+    })
+}
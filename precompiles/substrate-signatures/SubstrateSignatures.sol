@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: BSD-3-Clause
+
+pragma solidity >=0.7.0;
+
+/*
+ * @dev Substrate signature verification, the same interface for each scheme:
+ * - sr25519 at 0x0000000000000000000000000000000000005002;
+ * - ed25519 at 0x0000000000000000000000000000000000005003;
+ * - Substrate ECDSA at 0x0000000000000000000000000000000000005004.
+ */
+interface SubstrateSignatures {
+    /*
+     * @dev Verify signature of the message.
+     * @param public_key 32 bytes public key, 33 bytes compressed public key for ECDSA.
+     * @param signature 64 bytes signature, 65 bytes recoverable signature for ECDSA.
+     * @param message Signed message, it's hashed by blake2-256 before signing for ECDSA.
+     * @note Polkadot.js extensions wrap signed message in `<Bytes>` and `</Bytes>`.
+     */
+    function verify(
+        bytes calldata public_key,
+        bytes calldata signature,
+        bytes calldata message
+    ) external view returns (bool);
+}
@@ -0,0 +1,128 @@
+//! Verification of Substrate signatures: sr25519, ed25519 and ECDSA.
+//!
+//! Each scheme is a separate precompile with the same `verify(bytes,bytes,bytes)` function,
+//! see `SubstrateSignatures.sol`. Verification is done by `sp_io::crypto` host functions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{executor::PrecompileOutput, Context, ExitError, ExitSucceed};
+use pallet_evm::Precompile;
+use precompile_utils::{revert, selector, Bytes, EvmDataReader, EvmDataWriter, EvmResult, Gasometer};
+use sp_core::{ecdsa, ed25519, sr25519};
+use sp_std::{convert::TryInto, marker::PhantomData};
+
+#[cfg(test)]
+mod tests;
+
+// ======= SubstrateSignatures.sol:SubstrateSignatures =======
+const VERIFY: u32 = selector!("verify(bytes,bytes,bytes)");
+
+/// Gas cost of precompile call, signature verification and message are charged on top.
+pub const BASE_COST: u64 = 500;
+
+/// Gas cost of each 32 bytes word of the message.
+const MESSAGE_WORD_COST: u64 = 6;
+
+/// Substrate signature scheme.
+pub trait SignatureScheme {
+    /// Gas cost of signature verification without the message cost.
+    const BASE_COST: u64;
+
+    /// Verify `signature` of `message` by `public` key.
+    ///
+    /// Returns `None` when public key or signature has wrong length.
+    fn verify(public: &[u8], signature: &[u8], message: &[u8]) -> Option<bool>;
+}
+
+/// Schnorrkel signature scheme.
+pub enum Sr25519 {}
+
+impl SignatureScheme for Sr25519 {
+    const BASE_COST: u64 = 4_000;
+
+    fn verify(public: &[u8], signature: &[u8], message: &[u8]) -> Option<bool> {
+        let public = sr25519::Public::from_raw(public.try_into().ok()?);
+        let signature = sr25519::Signature::from_raw(signature.try_into().ok()?);
+        Some(sp_io::crypto::sr25519_verify(&signature, message, &public))
+    }
+}
+
+/// Edwards signature scheme.
+pub enum Ed25519 {}
+
+impl SignatureScheme for Ed25519 {
+    const BASE_COST: u64 = 3_000;
+
+    fn verify(public: &[u8], signature: &[u8], message: &[u8]) -> Option<bool> {
+        let public = ed25519::Public::from_raw(public.try_into().ok()?);
+        let signature = ed25519::Signature::from_raw(signature.try_into().ok()?);
+        Some(sp_io::crypto::ed25519_verify(&signature, message, &public))
+    }
+}
+
+/// Substrate ECDSA signature scheme, message is hashed by blake2-256.
+pub enum SubstrateEcdsa {}
+
+impl SignatureScheme for SubstrateEcdsa {
+    const BASE_COST: u64 = 3_000;
+
+    fn verify(public: &[u8], signature: &[u8], message: &[u8]) -> Option<bool> {
+        let public = ecdsa::Public::from_raw(public.try_into().ok()?);
+        let signature = ecdsa::Signature::from_raw(signature.try_into().ok()?);
+        Some(sp_io::crypto::ecdsa_verify(&signature, message, &public))
+    }
+}
+
+/// Signature verification precompile of `S` scheme.
+pub struct SignatureVerify<S>(PhantomData<S>);
+
+impl<S: SignatureScheme> SignatureVerify<S> {
+    fn verify(
+        input: &mut EvmDataReader,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PrecompileOutput> {
+        input.expect_arguments(3)?;
+        let public: Bytes = input.read()?;
+        let signature: Bytes = input.read()?;
+        let message: Bytes = input.read()?;
+
+        let words = (message.0.len() as u64).saturating_add(31) / 32;
+        let message_cost = MESSAGE_WORD_COST.saturating_mul(words);
+        gasometer.record_cost(S::BASE_COST.saturating_add(message_cost))?;
+
+        let valid = S::verify(&public.0, &signature.0, &message.0)
+            .ok_or_else(|| revert("Invalid public key or signature length"))?;
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            cost: gasometer.used_gas(),
+            output: EvmDataWriter::new().write(valid).build(),
+            logs: Default::default(),
+        })
+    }
+}
+
+impl<S: SignatureScheme> Precompile for SignatureVerify<S> {
+    fn execute(
+        input: &[u8],
+        target_gas: Option<u64>,
+        _context: &Context,
+    ) -> Result<PrecompileOutput, ExitError> {
+        let mut gasometer = Gasometer::new(target_gas);
+        gasometer.record_cost(BASE_COST)?;
+        gasometer.record_input_cost(input)?;
+        let (mut input, selector) = EvmDataReader::new_with_selector(input)?;
+
+        match selector {
+            VERIFY => Self::verify(&mut input, &mut gasometer),
+            _ => Err(revert("No method at given selector")),
+        }
+    }
+}
+
+/// sr25519 signature verification precompile.
+pub type Sr25519Verify = SignatureVerify<Sr25519>;
+/// ed25519 signature verification precompile.
+pub type Ed25519Verify = SignatureVerify<Ed25519>;
+/// Substrate ECDSA signature verification precompile.
+pub type SubstrateEcdsaVerify = SignatureVerify<SubstrateEcdsa>;
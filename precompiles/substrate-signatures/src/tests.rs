@@ -0,0 +1,79 @@
+use crate::*;
+use evm::{Context, ExitError};
+use pallet_evm::Precompile;
+use precompile_utils::INPUT_WORD_COST;
+use sp_core::{Pair, H160, U256};
+
+fn context() -> Context {
+    Context {
+        address: H160::zero(),
+        caller: H160::zero(),
+        apparent_value: U256::zero(),
+    }
+}
+
+fn verify_input(public: &[u8], signature: &[u8], message: &[u8]) -> Vec<u8> {
+    EvmDataWriter::new_with_selector(VERIFY)
+        .write(Bytes::from(public))
+        .write(Bytes::from(signature))
+        .write(Bytes::from(message))
+        .build()
+}
+
+fn verify<S: SignatureScheme>(public: &[u8], signature: &[u8], message: &[u8]) -> bool {
+    let input = verify_input(public, signature, message);
+    let output = SignatureVerify::<S>::execute(&input, None, &context()).unwrap();
+    let input_cost = (input.len() as u64 + 31) / 32 * INPUT_WORD_COST;
+    assert_eq!(
+        output.cost,
+        BASE_COST + input_cost + S::BASE_COST + MESSAGE_WORD_COST
+    );
+    EvmDataReader::new(&output.output).read::<bool>().unwrap()
+}
+
+#[test]
+fn sr25519_verify_is_ok() {
+    let pair = sr25519::Pair::from_seed(&[1; 32]);
+    let signature = pair.sign(b"Hello Astar");
+
+    assert!(verify::<Sr25519>(&pair.public().0, &signature.0, b"Hello Astar"));
+    assert!(!verify::<Sr25519>(&pair.public().0, &signature.0, b"Hello Shiden"));
+}
+
+#[test]
+fn ed25519_verify_is_ok() {
+    let pair = ed25519::Pair::from_seed(&[1; 32]);
+    let signature = pair.sign(b"Hello Astar");
+
+    assert!(verify::<Ed25519>(&pair.public().0, &signature.0, b"Hello Astar"));
+    assert!(!verify::<Ed25519>(&pair.public().0, &signature.0, b"Hello Shiden"));
+}
+
+#[test]
+fn substrate_ecdsa_verify_is_ok() {
+    let pair = ecdsa::Pair::from_seed(&[1; 32]);
+    let signature = pair.sign(b"Hello Astar");
+
+    assert!(verify::<SubstrateEcdsa>(&pair.public().0, &signature.0, b"Hello Astar"));
+    assert!(!verify::<SubstrateEcdsa>(&pair.public().0, &signature.0, b"Hello Shiden"));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    let pair = sr25519::Pair::from_seed(&[1; 32]);
+    let signature = pair.sign(b"Hello Astar");
+
+    // Ed25519 and sr25519 keys have the same length, ECDSA key is longer
+    let input = verify_input(&pair.public().0, &signature.0, b"Hello Astar");
+    assert!(SubstrateEcdsaVerify::execute(&input, None, &context()).is_err());
+
+    let input = verify_input(&pair.public().0, &signature.0[..63], b"Hello Astar");
+    assert!(Sr25519Verify::execute(&input, None, &context()).is_err());
+
+    // Out of gas
+    let input = verify_input(&pair.public().0, &signature.0, b"Hello Astar");
+    assert_eq!(
+        Sr25519Verify::execute(&input, Some(Sr25519::BASE_COST), &context()).err(),
+        Some(ExitError::OutOfGas)
+    );
+}
@@ -0,0 +1,26 @@
+//! Procedural macros of EVM precompile utils.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use sha3::{Digest, Keccak256};
+use syn::{parse_macro_input, LitStr};
+
+/// Solidity function selector of the function signature, e.g.
+/// `selector!("set_keys(bytes)") == 0xbcb24ddc_u32`.
+#[proc_macro]
+pub fn selector(input: TokenStream) -> TokenStream {
+    let signature = parse_macro_input!(input as LitStr);
+    let hash = Keccak256::digest(signature.value().as_bytes());
+    let selector = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+    quote!(#selector).into()
+}
+
+/// Keccak-256 hash of the string as `[u8; 32]`, e.g. event signature topic
+/// `keccak256!("Transfer(address,address,uint256)")`.
+#[proc_macro]
+pub fn keccak256(input: TokenStream) -> TokenStream {
+    let data = parse_macro_input!(input as LitStr);
+    let hash = Keccak256::digest(data.value().as_bytes());
+    let bytes = hash.iter();
+    quote!([#(#bytes),*]).into()
+}
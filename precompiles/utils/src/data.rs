@@ -0,0 +1,332 @@
+//! Solidity ABI encoding of precompile input and output.
+
+use crate::{revert, EvmResult};
+use sp_core::{H160, H256, U256};
+use sp_std::{convert::TryInto, vec, vec::Vec};
+
+/// Size of ABI word in bytes.
+const WORD_SIZE: usize = 32;
+
+/// Solidity `address` type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Address(pub H160);
+
+impl From<H160> for Address {
+    fn from(address: H160) -> Self {
+        Self(address)
+    }
+}
+
+impl From<Address> for H160 {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+/// Solidity `bytes` type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Bytes(pub Vec<u8>);
+
+impl From<Vec<u8>> for Bytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Bytes> for Vec<u8> {
+    fn from(bytes: Bytes) -> Self {
+        bytes.0
+    }
+}
+
+/// Reader of ABI-encoded input, all reads are bounds checked.
+#[derive(Clone, Copy, Debug)]
+pub struct EvmDataReader<'a> {
+    input: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> EvmDataReader<'a> {
+    /// Reader of ABI-encoded data without selector.
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { input, cursor: 0 }
+    }
+
+    /// Read function selector and create reader of its arguments.
+    pub fn new_with_selector(input: &'a [u8]) -> EvmResult<(Self, u32)> {
+        if input.len() < 4 {
+            return Err(revert("Input length less than 4 bytes"));
+        }
+
+        let mut selector = [0u8; 4];
+        selector.copy_from_slice(&input[0..4]);
+        Ok((Self::new(&input[4..]), u32::from_be_bytes(selector)))
+    }
+
+    /// Ensure that input contains at least `count` arguments.
+    pub fn expect_arguments(&self, count: usize) -> EvmResult {
+        if self.input.len() >= self.cursor.saturating_add(count.saturating_mul(WORD_SIZE)) {
+            Ok(())
+        } else {
+            Err(revert("Input doesn't match expected length"))
+        }
+    }
+
+    /// Read value of ABI type `T`.
+    pub fn read<T: EvmData>(&mut self) -> EvmResult<T> {
+        T::read(self)
+    }
+
+    /// Read `len` raw bytes and move the cursor.
+    pub fn read_raw_bytes(&mut self, len: usize) -> EvmResult<&'a [u8]> {
+        let range = self.move_cursor(len)?;
+        Ok(&self.input[range])
+    }
+
+    /// Read offset of the dynamic data and create reader of the pointed data.
+    pub fn read_pointer(&mut self) -> EvmResult<Self> {
+        let offset = self.read_usize()?;
+        self.input
+            .get(offset..)
+            .map(Self::new)
+            .ok_or_else(|| revert("Pointer points out of bounds"))
+    }
+
+    /// Read `uint256` that fits into `usize`, e.g. length or offset.
+    pub fn read_usize(&mut self) -> EvmResult<usize> {
+        let value: U256 = self.read()?;
+        if value > U256::from(usize::MAX) {
+            return Err(revert("Value is too large for length"));
+        }
+        Ok(value.low_u64() as usize)
+    }
+
+    fn move_cursor(&mut self, len: usize) -> EvmResult<sp_std::ops::Range<usize>> {
+        let start = self.cursor;
+        let end = start
+            .checked_add(len)
+            .filter(|end| *end <= self.input.len())
+            .ok_or_else(|| revert("Tried to read out of bounds"))?;
+        self.cursor = end;
+        Ok(start..end)
+    }
+}
+
+/// Writer of ABI-encoded output.
+#[derive(Clone, Debug, Default)]
+pub struct EvmDataWriter {
+    data: Vec<u8>,
+    offset_data: Vec<OffsetDatum>,
+    selector: Option<u32>,
+}
+
+/// Dynamic data written after the static part, pointed by the offset word.
+#[derive(Clone, Debug)]
+struct OffsetDatum {
+    /// Position of the offset word.
+    offset_position: usize,
+    /// Pointed data.
+    data: Vec<u8>,
+    /// Array items offsets are relative to the first item, not to the array length.
+    offset_shift: usize,
+}
+
+impl EvmDataWriter {
+    /// Writer of ABI-encoded data without selector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writer of ABI-encoded data prefixed by function selector, e.g. for events and errors.
+    pub fn new_with_selector(selector: u32) -> Self {
+        Self {
+            selector: Some(selector),
+            ..Self::default()
+        }
+    }
+
+    /// Write value of ABI type `T`.
+    pub fn write<T: EvmData>(mut self, value: T) -> Self {
+        T::write(&mut self, value);
+        self
+    }
+
+    /// Write raw bytes without padding.
+    pub fn write_raw_bytes(mut self, bytes: &[u8]) -> Self {
+        self.data.extend_from_slice(bytes);
+        self
+    }
+
+    /// Encoded data.
+    pub fn build(self) -> Vec<u8> {
+        let selector = self.selector;
+        let data = self.bake_data();
+        match selector {
+            Some(selector) => {
+                let mut output = selector.to_be_bytes().to_vec();
+                output.extend_from_slice(&data);
+                output
+            }
+            None => data,
+        }
+    }
+
+    /// Append dynamic data after static part and fill offsets.
+    fn bake_data(self) -> Vec<u8> {
+        let mut output = self.data;
+        for datum in self.offset_data {
+            let offset = U256::from(output.len() - datum.offset_shift);
+            offset.to_big_endian(
+                &mut output[datum.offset_position..datum.offset_position + WORD_SIZE],
+            );
+            output.extend_from_slice(&datum.data);
+        }
+        output
+    }
+
+    /// Write offset word pointing to `data` written after the static part.
+    fn write_pointer(&mut self, data: Vec<u8>) {
+        let offset_position = self.data.len();
+        self.data.extend_from_slice(&[0u8; WORD_SIZE]);
+        self.offset_data.push(OffsetDatum {
+            offset_position,
+            data,
+            offset_shift: 0,
+        });
+    }
+}
+
+/// Solidity ABI type.
+pub trait EvmData: Sized {
+    /// Read value, moves reader cursor.
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self>;
+
+    /// Write value.
+    fn write(writer: &mut EvmDataWriter, value: Self);
+}
+
+impl EvmData for H256 {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        reader.read_raw_bytes(WORD_SIZE).map(H256::from_slice)
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        writer.data.extend_from_slice(value.as_bytes());
+    }
+}
+
+impl EvmData for U256 {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        reader.read_raw_bytes(WORD_SIZE).map(U256::from_big_endian)
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        let mut word = [0u8; WORD_SIZE];
+        value.to_big_endian(&mut word);
+        writer.data.extend_from_slice(&word);
+    }
+}
+
+impl EvmData for Address {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        let word: H256 = reader.read()?;
+        if word[..12].iter().any(|byte| *byte != 0) {
+            return Err(revert("Address has non-zero high bytes"));
+        }
+        Ok(Address(H160::from_slice(&word[12..])))
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        H256::write(writer, value.0.into());
+    }
+}
+
+impl EvmData for bool {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        match reader.read::<U256>()? {
+            value if value.is_zero() => Ok(false),
+            value if value == U256::one() => Ok(true),
+            _ => Err(revert("Value is not a bool")),
+        }
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        U256::write(writer, if value { U256::one() } else { U256::zero() });
+    }
+}
+
+macro_rules! impl_evmdata_for_uints {
+    ($($uint:ty),*) => {
+        $(
+            impl EvmData for $uint {
+                fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+                    let value: U256 = reader.read()?;
+                    value
+                        .try_into()
+                        .map_err(|_| revert(concat!("Value is too large for ", stringify!($uint))))
+                }
+
+                fn write(writer: &mut EvmDataWriter, value: Self) {
+                    U256::write(writer, value.into());
+                }
+            }
+        )*
+    };
+}
+
+impl_evmdata_for_uints!(u8, u16, u32, u64, u128);
+
+impl EvmData for Bytes {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        let mut inner = reader.read_pointer()?;
+        let len = inner.read_usize()?;
+        inner.read_raw_bytes(len).map(Into::into)
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        let len = value.0.len();
+        let padded_len = len
+            .checked_add(WORD_SIZE - 1)
+            .map(|len| len / WORD_SIZE * WORD_SIZE)
+            .unwrap_or(len);
+
+        let mut data = vec![0u8; WORD_SIZE + padded_len];
+        U256::from(len).to_big_endian(&mut data[..WORD_SIZE]);
+        data[WORD_SIZE..WORD_SIZE + len].copy_from_slice(&value.0);
+        writer.write_pointer(data);
+    }
+}
+
+impl<T: EvmData> EvmData for Vec<T> {
+    fn read(reader: &mut EvmDataReader) -> EvmResult<Self> {
+        let mut inner = reader.read_pointer()?;
+        let len = inner.read_usize()?;
+        // Items offsets are relative to the first item
+        let mut items = EvmDataReader::new(&inner.input[inner.cursor..]);
+
+        // Each item takes at least one word, it prevents huge allocation
+        items.expect_arguments(len)?;
+        let mut array = Vec::with_capacity(len);
+        for _ in 0..len {
+            array.push(items.read()?);
+        }
+        Ok(array)
+    }
+
+    fn write(writer: &mut EvmDataWriter, value: Self) {
+        let mut inner = EvmDataWriter::new().write(U256::from(value.len()));
+        for item in value {
+            inner = inner.write(item);
+        }
+        for datum in &mut inner.offset_data {
+            datum.offset_shift += WORD_SIZE;
+        }
+        writer.write_pointer(inner.bake_data());
+    }
+}
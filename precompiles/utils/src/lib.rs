@@ -0,0 +1,208 @@
+//! Utilities to write Astar EVM precompiles.
+//!
+//! - `EvmDataReader` and `EvmDataWriter` for Solidity ABI input and output;
+//! - `selector!` and `keccak256!` macros to get function selector and event topic from signature;
+//! - `Gasometer` and `RuntimeHelper` for gas accounting of dispatched calls and storage access;
+//! - `log1`, `log2` and `log3` to build Ethereum logs.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use evm::{backend::Log, ExitError};
+use frame_support::{
+    dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    traits::Get,
+};
+use pallet_evm::GasWeightMapping;
+use sp_core::{H160, H256};
+use sp_std::{borrow::Cow, marker::PhantomData, string::String, vec, vec::Vec};
+
+mod data;
+pub use data::{Address, Bytes, EvmData, EvmDataReader, EvmDataWriter};
+pub use precompile_utils_macro::{keccak256, selector};
+
+#[cfg(test)]
+mod tests;
+
+/// Gas cost of each 32 bytes word of precompile input or output, the same as copy cost of
+/// `IDENTITY`.
+pub const INPUT_WORD_COST: u64 = 3;
+
+/// Result of precompile execution step.
+pub type EvmResult<T = ()> = Result<T, ExitError>;
+
+/// Revert precompile execution with the reason.
+///
+/// Note: EVM of this version can't return revert data from precompile, it only has
+/// `ExitError` to fail with. The reason is reported as exit error message, so contracts
+/// calling the precompile get empty revert data. ABI encoded `Error(string)` revert is left
+/// until Frontier precompiles are able to revert.
+pub fn revert(reason: impl Into<Cow<'static, str>>) -> ExitError {
+    ExitError::Other(reason.into())
+}
+
+/// Ethereum log of `address` with single topic, e.g. event signature.
+pub fn log1(address: H160, topic0: impl Into<H256>, data: Vec<u8>) -> Log {
+    Log {
+        address,
+        topics: vec![topic0.into()],
+        data,
+    }
+}
+
+/// Ethereum log of `address` with two topics, e.g. event signature and indexed argument.
+pub fn log2(
+    address: H160,
+    topic0: impl Into<H256>,
+    topic1: impl Into<H256>,
+    data: Vec<u8>,
+) -> Log {
+    Log {
+        address,
+        topics: vec![topic0.into(), topic1.into()],
+        data,
+    }
+}
+
+/// Ethereum log of `address` with three topics, e.g. event signature and two indexed arguments.
+pub fn log3(
+    address: H160,
+    topic0: impl Into<H256>,
+    topic1: impl Into<H256>,
+    topic2: impl Into<H256>,
+    data: Vec<u8>,
+) -> Log {
+    Log {
+        address,
+        topics: vec![topic0.into(), topic1.into(), topic2.into()],
+        data,
+    }
+}
+
+/// Gas accounting of precompile execution.
+#[derive(Clone, Copy, Debug)]
+pub struct Gasometer {
+    target_gas: Option<u64>,
+    used_gas: u64,
+}
+
+impl Gasometer {
+    /// Gasometer limited by `target_gas`, if any.
+    pub fn new(target_gas: Option<u64>) -> Self {
+        Self {
+            target_gas,
+            used_gas: 0,
+        }
+    }
+
+    /// Gas used so far.
+    pub fn used_gas(&self) -> u64 {
+        self.used_gas
+    }
+
+    /// Record cost, fails when gas limit is exceeded.
+    pub fn record_cost(&mut self, cost: u64) -> EvmResult {
+        self.used_gas = self
+            .used_gas
+            .checked_add(cost)
+            .ok_or(ExitError::OutOfGas)?;
+
+        match self.target_gas {
+            Some(gas_limit) if self.used_gas > gas_limit => Err(ExitError::OutOfGas),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record cost of reading `input` by words.
+    pub fn record_input_cost(&mut self, input: &[u8]) -> EvmResult {
+        let words = (input.len() as u64).saturating_add(31) / 32;
+        self.record_cost(INPUT_WORD_COST.saturating_mul(words))
+    }
+
+    /// Record cost of writing `output` by words, e.g. of variable length list.
+    pub fn record_output_cost(&mut self, output: &[u8]) -> EvmResult {
+        self.record_input_cost(output)
+    }
+
+    /// Record cost of the log as `LOG*` opcode does.
+    pub fn record_log_cost(&mut self, log: &Log) -> EvmResult {
+        // G_log + G_logtopic * topics + G_logdata * data bytes
+        let topics_cost = 375u64.saturating_mul(log.topics.len() as u64);
+        let data_cost = 8u64.saturating_mul(log.data.len() as u64);
+        self.record_cost(375u64.saturating_add(topics_cost).saturating_add(data_cost))
+    }
+
+    /// Gas left, `None` when execution isn't limited.
+    pub fn remaining_gas(&self) -> EvmResult<Option<u64>> {
+        self.target_gas
+            .map(|gas_limit| gas_limit.checked_sub(self.used_gas).ok_or(ExitError::OutOfGas))
+            .transpose()
+    }
+}
+
+/// Runtime calls and storage reads from precompiles.
+pub struct RuntimeHelper<R>(PhantomData<R>);
+
+impl<R> RuntimeHelper<R>
+where
+    R: pallet_evm::Config,
+    R::Call: Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo,
+    <R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
+{
+    /// Dispatch `call` from `origin` and record gas of its actual weight.
+    ///
+    /// Fails without dispatch when gas left isn't enough for the call weight. Failed dispatch
+    /// reverts with the error name, e.g. module error `NotCandidate`.
+    pub fn try_dispatch<Call>(
+        origin: Option<R::AccountId>,
+        call: Call,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PostDispatchInfo>
+    where
+        R::Call: From<Call>,
+    {
+        Self::try_dispatch_origin(origin.into(), R::Call::from(call), gasometer)
+    }
+
+    /// Dispatch `call` from arbitrary `origin`, e.g. one with additional call filter.
+    ///
+    /// Gas is recorded the same way as `try_dispatch` does.
+    pub fn try_dispatch_origin(
+        origin: <R::Call as Dispatchable>::Origin,
+        call: R::Call,
+        gasometer: &mut Gasometer,
+    ) -> EvmResult<PostDispatchInfo> {
+        let info = call.get_dispatch_info();
+
+        if let Some(gas_limit) = gasometer.remaining_gas()? {
+            let required_gas = R::GasWeightMapping::weight_to_gas(info.weight);
+            if required_gas > gas_limit {
+                return Err(ExitError::OutOfGas);
+            }
+        }
+
+        let post_info = call.dispatch(origin).map_err(|e| {
+            let error: &'static str = e.error.into();
+            let mut reason = String::from("Dispatched call failed: ");
+            reason.push_str(error);
+            revert(reason)
+        })?;
+
+        // Actual weight is refunded, but never more than the call weight is charged
+        gasometer.record_cost(R::GasWeightMapping::weight_to_gas(
+            post_info.calc_actual_weight(&info),
+        ))?;
+        Ok(post_info)
+    }
+}
+
+impl<R: pallet_evm::Config> RuntimeHelper<R> {
+    /// Gas cost of single storage read.
+    pub fn db_read_gas_cost() -> u64 {
+        R::GasWeightMapping::weight_to_gas(<R as frame_system::Config>::DbWeight::get().read)
+    }
+
+    /// Gas cost of single storage write.
+    pub fn db_write_gas_cost() -> u64 {
+        R::GasWeightMapping::weight_to_gas(<R as frame_system::Config>::DbWeight::get().write)
+    }
+}
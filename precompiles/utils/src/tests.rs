@@ -0,0 +1,191 @@
+use crate::*;
+use evm::ExitError;
+use hex_literal::hex;
+use sp_core::{H160, H256, U256};
+
+#[test]
+fn selector_is_ok() {
+    assert_eq!(selector!("set_keys(bytes)"), 0xbcb24ddc);
+    assert_eq!(selector!("purge_keys()"), 0x321c9b7a);
+    assert_eq!(selector!("register_as_candidate()"), 0xd09b6ba5);
+}
+
+#[test]
+fn keccak256_is_ok() {
+    assert_eq!(
+        keccak256!("Transfer(address,address,uint256)"),
+        hex!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"),
+    );
+}
+
+#[test]
+fn read_bytes_with_selector() {
+    let input = hex!(
+        "bcb24ddc
+        0000000000000000000000000000000000000000000000000000000000000020
+        0000000000000000000000000000000000000000000000000000000000000002
+        1234000000000000000000000000000000000000000000000000000000000000"
+    );
+    let (mut reader, selector) = EvmDataReader::new_with_selector(&input).unwrap();
+    assert_eq!(selector, 0xbcb24ddc);
+    assert!(reader.expect_arguments(1).is_ok());
+    assert_eq!(reader.read::<Bytes>(), Ok(Bytes(vec![0x12, 0x34])));
+}
+
+#[test]
+fn malformed_input_is_rejected() {
+    assert!(EvmDataReader::new_with_selector(&[0xbc, 0xb2, 0x4d]).is_err());
+
+    // Bytes length is larger than input
+    let input = hex!(
+        "0000000000000000000000000000000000000000000000000000000000000020
+        00000000000000000000000000000000000000000000000000000000000000ff
+        1234000000000000000000000000000000000000000000000000000000000000"
+    );
+    assert!(EvmDataReader::new(&input).read::<Bytes>().is_err());
+
+    // Bytes length overflows
+    let input = hex!(
+        "0000000000000000000000000000000000000000000000000000000000000020
+        ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+    );
+    assert!(EvmDataReader::new(&input).read::<Bytes>().is_err());
+
+    // Pointer is out of bounds
+    let input = hex!("0000000000000000000000000000000000000000000000000000000000000040");
+    assert!(EvmDataReader::new(&input).read::<Bytes>().is_err());
+
+    // Array is longer than input
+    let input = hex!(
+        "0000000000000000000000000000000000000000000000000000000000000020
+        0000000000000000000000000000000000000000000000000000000000000002
+        000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266"
+    );
+    assert!(EvmDataReader::new(&input).read::<Vec<Address>>().is_err());
+
+    // Address with non-zero high bytes
+    let input = hex!("ff0000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266");
+    assert!(EvmDataReader::new(&input).read::<Address>().is_err());
+
+    // Not enough arguments
+    assert!(EvmDataReader::new(&input).expect_arguments(2).is_err());
+}
+
+#[test]
+fn write_static_and_dynamic_data() {
+    let address = H160::from(hex!["f39fd6e51aad88f6f4ce6ab8827279cfffb92266"]);
+    let output = EvmDataWriter::new()
+        .write(Address(address))
+        .write(Bytes(vec![0x12, 0x34]))
+        .write(U256::from(5))
+        .build();
+    assert_eq!(
+        output,
+        hex!(
+            "000000000000000000000000f39fd6e51aad88f6f4ce6ab8827279cfffb92266
+            0000000000000000000000000000000000000000000000000000000000000060
+            0000000000000000000000000000000000000000000000000000000000000005
+            0000000000000000000000000000000000000000000000000000000000000002
+            1234000000000000000000000000000000000000000000000000000000000000"
+        )
+        .to_vec(),
+    );
+
+    let mut reader = EvmDataReader::new(&output);
+    assert_eq!(reader.read::<Address>(), Ok(Address(address)));
+    assert_eq!(reader.read::<Bytes>(), Ok(Bytes(vec![0x12, 0x34])));
+    assert_eq!(reader.read::<U256>(), Ok(U256::from(5)));
+}
+
+#[test]
+fn write_arrays() {
+    let addresses = vec![
+        Address(H160::repeat_byte(0x11)),
+        Address(H160::repeat_byte(0x22)),
+    ];
+    let output = EvmDataWriter::new().write(addresses.clone()).build();
+    assert_eq!(
+        output,
+        hex!(
+            "0000000000000000000000000000000000000000000000000000000000000020
+            0000000000000000000000000000000000000000000000000000000000000002
+            0000000000000000000000001111111111111111111111111111111111111111
+            0000000000000000000000002222222222222222222222222222222222222222"
+        )
+        .to_vec(),
+    );
+    assert_eq!(EvmDataReader::new(&output).read::<Vec<Address>>(), Ok(addresses));
+
+    // Offsets of dynamic items are relative to the first item
+    let keys = vec![Bytes(vec![0x12]), Bytes(vec![0x34, 0x56])];
+    let output = EvmDataWriter::new().write(keys.clone()).build();
+    assert_eq!(
+        output,
+        hex!(
+            "0000000000000000000000000000000000000000000000000000000000000020
+            0000000000000000000000000000000000000000000000000000000000000002
+            0000000000000000000000000000000000000000000000000000000000000040
+            0000000000000000000000000000000000000000000000000000000000000080
+            0000000000000000000000000000000000000000000000000000000000000001
+            1200000000000000000000000000000000000000000000000000000000000000
+            0000000000000000000000000000000000000000000000000000000000000002
+            3456000000000000000000000000000000000000000000000000000000000000"
+        )
+        .to_vec(),
+    );
+    assert_eq!(EvmDataReader::new(&output).read::<Vec<Bytes>>(), Ok(keys));
+}
+
+#[test]
+fn revert_is_exit_error() {
+    assert_eq!(revert("Not enough"), ExitError::Other("Not enough".into()));
+}
+
+#[test]
+fn gasometer_is_ok() {
+    let mut gasometer = Gasometer::new(Some(100));
+    assert_eq!(gasometer.record_cost(60), Ok(()));
+    assert_eq!(gasometer.remaining_gas(), Ok(Some(40)));
+    assert_eq!(gasometer.record_cost(41), Err(ExitError::OutOfGas));
+
+    let mut gasometer = Gasometer::new(None);
+    assert_eq!(gasometer.record_cost(u64::MAX), Ok(()));
+    assert_eq!(gasometer.remaining_gas(), Ok(None));
+    assert_eq!(gasometer.used_gas(), u64::MAX);
+
+    let log = log3(
+        H160::zero(),
+        H256::zero(),
+        H256::zero(),
+        H256::zero(),
+        vec![0u8; 32],
+    );
+    let mut gasometer = Gasometer::new(None);
+    assert_eq!(gasometer.record_log_cost(&log), Ok(()));
+    assert_eq!(gasometer.used_gas(), 375 + 3 * 375 + 8 * 32);
+
+    // Input and output costs are rounded up to words
+    let mut gasometer = Gasometer::new(None);
+    assert_eq!(gasometer.record_input_cost(&[0u8; 36]), Ok(()));
+    assert_eq!(gasometer.used_gas(), 2 * INPUT_WORD_COST);
+    assert_eq!(gasometer.record_output_cost(&[0u8; 64]), Ok(()));
+    assert_eq!(gasometer.used_gas(), 4 * INPUT_WORD_COST);
+}
+
+#[test]
+fn log_topics_are_ok() {
+    let address = H160::repeat_byte(0x11);
+    let topic = H256::repeat_byte(0x22);
+    let caller = H160::repeat_byte(0x33);
+
+    let log = log1(address, topic, vec![]);
+    assert_eq!(log.address, address);
+    assert_eq!(log.topics, vec![topic]);
+
+    // Address topic is left-padded with zeros
+    let log = log2(address, topic, caller, vec![1]);
+    let mut caller_topic = [0u8; 32];
+    caller_topic[12..].copy_from_slice(caller.as_bytes());
+    assert_eq!(log.topics, vec![topic, H256(caller_topic)]);
+    assert_eq!(log.data, vec![1]);
+}
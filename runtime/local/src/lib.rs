@@ -63,10 +63,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("astar-local"),
     impl_name: create_runtime_str!("astar-local"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 impl_opaque_keys! {
@@ -219,7 +219,7 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
     /// A timestamp: milliseconds since the unix epoch.
     type Moment = u64;
-    type OnTimestampSet = (Aura, BlockReward);
+    type OnTimestampSet = Aura;
     type MinimumPeriod = MinimumPeriod;
     type WeightInfo = ();
 }
@@ -268,7 +268,7 @@ type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 pub struct OnBlockReward;
 impl OnUnbalanced<NegativeImbalance> for OnBlockReward {
     fn on_nonzero_unbalanced(amount: NegativeImbalance) {
-        let (dapps, maintain) = amount.ration(50, 50);
+        let (dapps, maintain) = amount.ration(30, 50);
 
         // dapp staking block reward
         DappsStaking::on_unbalanced(dapps);
@@ -282,12 +282,25 @@ impl OnUnbalanced<NegativeImbalance> for OnBlockReward {
 
 parameter_types! {
     pub const RewardAmount: Balance = 2_664 * MILLIAST;
+    pub const IdealTvlPercentage: Perbill = Perbill::from_percent(50);
+    // 20% of block reward for dapps staking depends on TVL, the rest 30% is fixed
+    pub const TvlRewardPortion: Perbill = Perbill::from_percent(20);
+    pub const MaxScheduleItems: u32 = 32;
 }
 
 impl pallet_block_reward::Config for Runtime {
+    type Event = Event;
     type Currency = Balances;
     type OnBlockReward = OnBlockReward;
     type RewardAmount = RewardAmount;
+    type TvlProvider = DappsStaking;
+    type IdealTvlPercentage = IdealTvlPercentage;
+    type TvlRewardPortion = TvlRewardPortion;
+    type OnTvlAdjustedReward = DappsStaking;
+    // unused part of TVL adjusted reward isn't minted
+    type OnUnusedTvlReward = ();
+    type MaxScheduleItems = MaxScheduleItems;
+    type WeightInfo = ();
 }
 
 parameter_types! {
@@ -418,6 +431,14 @@ parameter_types! {
     );
 }
 
+/// Runtime calls allowed to dispatch via EVM precompile, base call filter is applied as well.
+pub struct DispatchPrecompileFilter;
+impl frame_support::traits::Contains<Call> for DispatchPrecompileFilter {
+    fn contains(call: &Call) -> bool {
+        matches!(call, Call::Balances(_) | Call::Utility(_) | Call::DappsStaking(_))
+    }
+}
+
 impl pallet_evm::Config for Runtime {
     type FeeCalculator = FixedGasPrice;
     type GasWeightMapping = LocalGasWeightMapping;
@@ -435,6 +456,11 @@ impl pallet_evm::Config for Runtime {
     type FindAuthor = FindAuthorTruncated<Aura>;
 }
 
+impl pallet_precompile_registry::Config for Runtime {
+    type Event = Event;
+    type Precompiles = LocalNetworkPrecompiles<Self>;
+}
+
 pub struct TransactionConverter;
 
 impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
@@ -464,21 +490,87 @@ impl pallet_ethereum::Config for Runtime {
     type StateRoot = pallet_ethereum::IntermediateStateRoot;
 }
 
+/// ERC-20 metadata of the native currency.
+pub struct NativeCurrencyMetadata;
+impl pallet_precompile_balances_erc20::Erc20Metadata for NativeCurrencyMetadata {
+    fn name() -> &'static str {
+        "Local"
+    }
+
+    fn symbol() -> &'static str {
+        "LUKE"
+    }
+
+    fn decimals() -> u8 {
+        18
+    }
+}
+
+impl pallet_precompile_balances_erc20::Config for Runtime {
+    type Metadata = NativeCurrencyMetadata;
+}
+
+impl pallet_address_registry::Config for Runtime {
+    type Event = Event;
+}
+
 parameter_types! {
     pub const EcdsaUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
-    pub const CallFee: Balance = AST / 10;
     pub const CallMagicNumber: u16 = 0xff51;
 }
 
+/// EIP-712 typed data parameters of `EthCall` pallet.
+pub struct EthCallDomain;
+impl pallet_custom_signatures::eip712::Eip712Domain for EthCallDomain {
+    fn name() -> Vec<u8> {
+        b"Local".to_vec()
+    }
+    fn chain_id() -> u64 {
+        ChainId::get()
+    }
+    fn pallet_index() -> u8 {
+        <EthCall as frame_support::traits::PalletInfoAccess>::index() as u8
+    }
+    fn call_fee(call: &[u8]) -> u128 {
+        EthCall::query_payload_fee(call).unwrap_or_default()
+    }
+}
+
+/// EVM address mapping of `EthCall` signers.
+pub struct EvmAddressMapping;
+impl pallet_custom_signatures::evm::AddressMapping<AccountId> for EvmAddressMapping {
+    fn into_account_id(address: H160) -> AccountId {
+        <<Runtime as pallet_evm::Config>::AddressMapping as pallet_evm::AddressMapping<
+            AccountId,
+        >>::into_account_id(address)
+    }
+}
+
+/// Calls which fees are paid by open sponsors.
+pub struct SponsoredCalls;
+impl frame_support::traits::Contains<Call> for SponsoredCalls {
+    fn contains(call: &Call) -> bool {
+        matches!(call, Call::Balances(_))
+    }
+}
+
 impl pallet_custom_signatures::Config for Runtime {
     type Event = Event;
     type Call = Call;
-    type Signature = pallet_custom_signatures::ethereum::EthereumSignature;
-    type Signer = <Signature as Verify>::Signer;
+    // signer account is the same as EVM account of Ethereum address,
+    // signature scheme is selected by the leading tag byte
+    type Signature = pallet_custom_signatures::evm::EvmSignature<
+        pallet_custom_signatures::multi::AnySignature<EthCallDomain>,
+        EvmAddressMapping,
+    >;
+    type Signer = pallet_custom_signatures::evm::EvmSigner<EvmAddressMapping>;
     type CallMagicNumber = CallMagicNumber;
     type Currency = Balances;
-    type CallFee = CallFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type TransactionByteFee = TransactionByteFee;
     type OnChargeTransaction = ();
+    type ClaimAccount = pallet_custom_signatures::evm::LegacyToEvmAccount<EvmAddressMapping>;
+    type SponsoredCalls = SponsoredCalls;
     type UnsignedPriority = EcdsaUnsignedPriority;
 }
 
@@ -557,11 +649,14 @@ construct_runtime!(
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
         Vesting: pallet_vesting::{Pallet, Call, Storage, Config<T>, Event<T>},
         DappsStaking: pallet_dapps_staking::{Pallet, Call, Storage, Event<T>},
-        BlockReward: pallet_block_reward::{Pallet},
+        BlockReward: pallet_block_reward::{Pallet, Call, Storage, Event<T>},
         TransactionPayment: pallet_transaction_payment::{Pallet, Storage},
         EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>},
         Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Origin, Config},
-        EthCall: pallet_custom_signatures::{Pallet, Call, Event<T>, ValidateUnsigned},
+        BalancesErc20: pallet_precompile_balances_erc20::{Pallet, Storage},
+        AddressRegistry: pallet_address_registry::{Pallet, Call, Storage, Event<T>},
+        PrecompileRegistry: pallet_precompile_registry::{Pallet, Call, Storage, Event<T>, Config},
+        EthCall: pallet_custom_signatures::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
         Contracts: pallet_contracts::{Pallet, Call, Storage, Event<T>},
         Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
     }
@@ -794,6 +889,18 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_block_reward_runtime_api::BlockRewardApi<Block, BlockNumber, Balance> for Runtime {
+        fn block_reward(at: BlockNumber) -> Balance {
+            BlockReward::reward_at(at)
+        }
+    }
+
+    impl pallet_custom_signatures_runtime_api::CustomSignaturesApi<Block, Call, Balance> for Runtime {
+        fn query_call_fee(call: Call) -> Balance {
+            EthCall::query_call_fee(&call)
+        }
+    }
+
     impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
         fn chain_id() -> u64 {
             ChainId::get()
@@ -966,6 +1073,7 @@ impl_runtime_apis! {
 
             let mut list = Vec::<BenchmarkList>::new();
 
+            list_benchmark!(list, extra, pallet_block_reward, BlockReward);
             list_benchmark!(list, extra, pallet_dapps_staking, DappsStaking);
 
             let storage_info = AllPalletsWithSystem::storage_info();
@@ -998,6 +1106,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+            add_benchmark!(params, batches, pallet_block_reward, BlockReward);
             add_benchmark!(params, batches, pallet_dapps_staking, DappsStaking);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
@@ -86,10 +86,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("shiden"),
     impl_name: create_runtime_str!("shiden"),
     authoring_version: 1,
-    spec_version: 34,
+    spec_version: 35,
     impl_version: 0,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 2,
+    transaction_version: 3,
 };
 
 /// Native version.
@@ -146,7 +146,7 @@ impl Contains<Call> for BaseFilter {
     fn contains(call: &Call) -> bool {
         match call {
             // These modules are not allowed to be called by transactions:
-            // To leave collator just shutdown it, next session funds will be released
+            // Collators leave via CollatorExit, the bond is released after cooldown
             Call::CollatorSelection(pallet_collator_selection::Call::leave_intent { .. }) => false,
             // Other modules should works:
             _ => true,
@@ -200,7 +200,7 @@ parameter_types! {
 impl pallet_timestamp::Config for Runtime {
     /// A timestamp: milliseconds since the unix epoch.
     type Moment = u64;
-    type OnTimestampSet = BlockReward;
+    type OnTimestampSet = ();
     type MinimumPeriod = MinimumPeriod;
     type WeightInfo = ();
 }
@@ -249,19 +249,40 @@ impl pallet_multisig::Config for Runtime {
 
 parameter_types! {
     pub const EcdsaUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
-    pub const CallFee: Balance = SDN / 10;
     pub const CallMagicNumber: u16 = 0x0150;
 }
 
+/// EIP-712 typed data parameters of `EthCall` pallet.
+pub struct EthCallDomain;
+impl pallet_custom_signatures::eip712::Eip712Domain for EthCallDomain {
+    fn name() -> Vec<u8> {
+        b"Shiden".to_vec()
+    }
+    fn chain_id() -> u64 {
+        ChainId::get()
+    }
+    fn pallet_index() -> u8 {
+        <EthCall as frame_support::traits::PalletInfoAccess>::index() as u8
+    }
+    fn call_fee(call: &[u8]) -> u128 {
+        EthCall::query_payload_fee(call).unwrap_or_default()
+    }
+}
+
 impl pallet_custom_signatures::Config for Runtime {
     type Event = Event;
     type Call = Call;
-    type Signature = pallet_custom_signatures::ethereum::EthereumSignature;
+    type Signature = pallet_custom_signatures::eip712::Eip712Signature<EthCallDomain>;
     type Signer = <Signature as Verify>::Signer;
     type CallMagicNumber = CallMagicNumber;
     type Currency = Balances;
-    type CallFee = CallFee;
+    type WeightToFee = WeightToFee;
+    type TransactionByteFee = TransactionByteFee;
     type OnChargeTransaction = ToStakingPot;
+    // accounts claim is disabled
+    type ClaimAccount = ();
+    // open fees sponsorship is disabled
+    type SponsoredCalls = frame_support::traits::Nothing;
     type UnsignedPriority = EcdsaUnsignedPriority;
 }
 
@@ -371,7 +392,7 @@ impl pallet_authorship::Config for Runtime {
     type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
     type UncleGenerations = UncleGenerations;
     type FilterUncle = ();
-    type EventHandler = (CollatorSelection,);
+    type EventHandler = (CollatorSelection, BlockReward);
 }
 
 parameter_types! {
@@ -414,6 +435,16 @@ impl pallet_collator_selection::Config for Runtime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    // Leaving collator stays in the current and the queued validator sets
+    pub const CollatorExitCooldown: u32 = 2;
+}
+
+impl pallet_collator_exit::Config for Runtime {
+    type Event = Event;
+    type Cooldown = CollatorExitCooldown;
+}
+
 parameter_types! {
     pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
     pub const DappsStakingPalletId: PalletId = PalletId(*b"py/dpsst");
@@ -429,6 +460,19 @@ impl OnUnbalanced<NegativeImbalance> for ToStakingPot {
     }
 }
 
+parameter_types! {
+    // block author reward is disabled, collators reward goes to the staking pot
+    pub const AuthorRewardPercentage: Perbill = Perbill::from_percent(0);
+}
+
+/// Pays `AuthorRewardPercentage` of collators reward to the block author, the rest to the pot.
+type ToBlockAuthor = pallet_block_reward::author::AuthorReward<
+    Runtime,
+    Balances,
+    AuthorRewardPercentage,
+    ToStakingPot,
+>;
+
 pub struct OnBlockReward;
 impl OnUnbalanced<NegativeImbalance> for OnBlockReward {
     fn on_nonzero_unbalanced(amount: NegativeImbalance) {
@@ -440,18 +484,31 @@ impl OnUnbalanced<NegativeImbalance> for OnBlockReward {
         // treasury slice of block reward
         Balances::resolve_creating(&TreasuryPalletId::get().into_account(), treasury);
         // collators block reward
-        ToStakingPot::on_unbalanced(collators);
+        ToBlockAuthor::on_unbalanced(collators);
     }
 }
 
 parameter_types! {
     pub const RewardAmount: Balance = 2_664 * MILLISDN;
+    pub const IdealTvlPercentage: Perbill = Perbill::from_percent(50);
+    // TVL adjusted reward is disabled, whole block reward goes to `OnBlockReward`
+    pub const TvlRewardPortion: Perbill = Perbill::from_percent(0);
+    pub const MaxScheduleItems: u32 = 32;
 }
 
 impl pallet_block_reward::Config for Runtime {
+    type Event = Event;
     type Currency = Balances;
     type OnBlockReward = OnBlockReward;
     type RewardAmount = RewardAmount;
+    type TvlProvider = DappsStaking;
+    type IdealTvlPercentage = IdealTvlPercentage;
+    type TvlRewardPortion = TvlRewardPortion;
+    type OnTvlAdjustedReward = DappsStaking;
+    // unused part of TVL adjusted reward isn't minted
+    type OnUnusedTvlReward = ();
+    type MaxScheduleItems = MaxScheduleItems;
+    type WeightInfo = ();
 }
 
 parameter_types! {
@@ -608,6 +665,14 @@ parameter_types! {
     );
 }
 
+/// Runtime calls allowed to dispatch via EVM precompile, base call filter is applied as well.
+pub struct DispatchPrecompileFilter;
+impl Contains<Call> for DispatchPrecompileFilter {
+    fn contains(call: &Call) -> bool {
+        matches!(call, Call::Balances(_) | Call::Utility(_) | Call::DappsStaking(_))
+    }
+}
+
 impl pallet_evm::Config for Runtime {
     type FeeCalculator = FixedGasPrice;
     type GasWeightMapping = ShidenGasWeightMapping;
@@ -625,6 +690,11 @@ impl pallet_evm::Config for Runtime {
     type FindAuthor = FindAuthorTruncated<Aura>;
 }
 
+impl pallet_precompile_registry::Config for Runtime {
+    type Event = Event;
+    type Precompiles = ShidenNetworkPrecompiles<Self>;
+}
+
 pub struct TransactionConverter;
 
 impl fp_rpc::ConvertTransaction<UncheckedExtrinsic> for TransactionConverter {
@@ -654,6 +724,30 @@ impl pallet_ethereum::Config for Runtime {
     type StateRoot = pallet_ethereum::IntermediateStateRoot;
 }
 
+/// ERC-20 metadata of the native currency.
+pub struct NativeCurrencyMetadata;
+impl pallet_precompile_balances_erc20::Erc20Metadata for NativeCurrencyMetadata {
+    fn name() -> &'static str {
+        "Shiden"
+    }
+
+    fn symbol() -> &'static str {
+        "SDN"
+    }
+
+    fn decimals() -> u8 {
+        18
+    }
+}
+
+impl pallet_precompile_balances_erc20::Config for Runtime {
+    type Metadata = NativeCurrencyMetadata;
+}
+
+impl pallet_address_registry::Config for Runtime {
+    type Event = Event;
+}
+
 impl pallet_sudo::Config for Runtime {
     type Event = Event;
     type Call = Call;
@@ -677,7 +771,7 @@ construct_runtime!(
         TransactionPayment: pallet_transaction_payment::{Pallet, Storage} = 30,
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 31,
         Vesting: pallet_vesting::{Pallet, Call, Storage, Config<T>, Event<T>} = 32,
-        BlockReward: pallet_block_reward::{Pallet} = 33,
+        BlockReward: pallet_block_reward::{Pallet, Call, Storage, Event<T>} = 33,
         DappsStaking: pallet_dapps_staking::{Pallet, Call, Storage, Event<T>} = 34,
 
         Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent} = 40,
@@ -685,10 +779,14 @@ construct_runtime!(
         Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>} = 42,
         Aura: pallet_aura::{Pallet, Storage, Config<T>} = 43,
         AuraExt: cumulus_pallet_aura_ext::{Pallet, Storage, Config} = 44,
+        CollatorExit: pallet_collator_exit::{Pallet, Call, Storage, Event<T>} = 45,
 
         EVM: pallet_evm::{Pallet, Config, Call, Storage, Event<T>} = 60,
         Ethereum: pallet_ethereum::{Pallet, Call, Storage, Event, Origin, Config} = 61,
-        EthCall: pallet_custom_signatures::{Pallet, Call, Event<T>, ValidateUnsigned} = 62,
+        EthCall: pallet_custom_signatures::{Pallet, Call, Storage, Event<T>, ValidateUnsigned} = 62,
+        BalancesErc20: pallet_precompile_balances_erc20::{Pallet, Storage} = 63,
+        AddressRegistry: pallet_address_registry::{Pallet, Call, Storage, Event<T>} = 64,
+        PrecompileRegistry: pallet_precompile_registry::{Pallet, Call, Storage, Event<T>, Config} = 65,
 
         Sudo: pallet_sudo::{Pallet, Call, Storage, Event<T>, Config<T>} = 99,
     }
@@ -873,6 +971,18 @@ impl_runtime_apis! {
         }
     }
 
+    impl pallet_block_reward_runtime_api::BlockRewardApi<Block, BlockNumber, Balance> for Runtime {
+        fn block_reward(at: BlockNumber) -> Balance {
+            BlockReward::reward_at(at)
+        }
+    }
+
+    impl pallet_custom_signatures_runtime_api::CustomSignaturesApi<Block, Call, Balance> for Runtime {
+        fn query_call_fee(call: Call) -> Balance {
+            EthCall::query_call_fee(&call)
+        }
+    }
+
     impl sp_session::SessionKeys<Block> for Runtime {
         fn generate_session_keys(seed: Option<Vec<u8>>) -> Vec<u8> {
             SessionKeys::generate(seed)
@@ -1028,6 +1138,7 @@ impl_runtime_apis! {
 
             let mut list = Vec::<BenchmarkList>::new();
 
+            list_benchmark!(list, extra, pallet_block_reward, BlockReward);
             list_benchmark!(list, extra, pallet_dapps_staking, DappsStaking);
 
             let storage_info = AllPalletsWithSystem::storage_info();
@@ -1060,6 +1171,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, frame_system, SystemBench::<Runtime>);
             add_benchmark!(params, batches, pallet_balances, Balances);
             add_benchmark!(params, batches, pallet_timestamp, Timestamp);
+            add_benchmark!(params, batches, pallet_block_reward, BlockReward);
             add_benchmark!(params, batches, pallet_dapps_staking, DappsStaking);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
@@ -3,74 +3,68 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Decode;
-use evm::{executor::PrecompileOutput, Context, ExitError};
-use frame_support::dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo};
-use pallet_evm::{Precompile, PrecompileSet};
+use frame_support::{
+    dispatch::{Dispatchable, GetDispatchInfo, PostDispatchInfo},
+    traits::{Contains, OriginTrait},
+};
 use pallet_evm_precompile_bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
-use pallet_evm_precompile_dispatch::Dispatch;
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use pallet_precompile_account_mapping::AccountMapping;
+use pallet_precompile_balances_erc20::BalancesErc20;
+use pallet_precompile_dispatch_filtered::FilteredDispatch;
+use pallet_precompile_registry::precompile_set;
 use pallet_precompile_staking::Staking;
-use sp_core::H160;
-use sp_std::fmt::Debug;
-use sp_std::marker::PhantomData;
+use pallet_precompile_substrate_signatures::{Ed25519Verify, Sr25519Verify, SubstrateEcdsaVerify};
+use sp_std::convert::TryFrom;
 
-/// The PrecompileSet installed in the Shiden runtime.
-#[derive(Debug, Clone, Copy)]
-pub struct ShidenNetworkPrecompiles<R>(PhantomData<R>);
-
-impl<R> ShidenNetworkPrecompiles<R> {
-    /// Return all addresses that contain precompiles. This can be used to populate dummy code
-    /// under the precompile.
-    pub fn used_addresses<AccountId: From<H160>>() -> impl Iterator<Item = AccountId> {
-        sp_std::vec![1, 2, 3, 4, 5, 6, 7, 8, 1024, 1025, 1026, 20480]
-            .into_iter()
-            .map(|x| hash(x).into())
-    }
-}
-
-/// The following distribution has been decided for the precompiles
-/// 0-1023: Ethereum Mainnet Precompiles
-/// 1024-2047 Precompiles that are not in Ethereum Mainnet
-impl<R> PrecompileSet for ShidenNetworkPrecompiles<R>
-where
-    R: pallet_evm::Config + pallet_session::Config + pallet_collator_selection::Config,
-    <R::Call as Dispatchable>::Origin: From<Option<R::AccountId>>,
-    R::Call: From<pallet_session::Call<R>>
-        + From<pallet_collator_selection::Call<R>>
-        + Dispatchable<PostInfo = PostDispatchInfo>
-        + GetDispatchInfo
-        + Decode,
-{
-    fn execute(
-        address: H160,
-        input: &[u8],
-        target_gas: Option<u64>,
-        context: &Context,
-    ) -> Option<Result<PrecompileOutput, ExitError>> {
-        match address {
-            // Ethereum precompiles :
-            a if a == hash(1) => Some(ECRecover::execute(input, target_gas, context)),
-            a if a == hash(2) => Some(Sha256::execute(input, target_gas, context)),
-            a if a == hash(3) => Some(Ripemd160::execute(input, target_gas, context)),
-            a if a == hash(4) => Some(Identity::execute(input, target_gas, context)),
-            a if a == hash(5) => Some(Modexp::execute(input, target_gas, context)),
-            a if a == hash(6) => Some(Bn128Add::execute(input, target_gas, context)),
-            a if a == hash(7) => Some(Bn128Mul::execute(input, target_gas, context)),
-            a if a == hash(8) => Some(Bn128Pairing::execute(input, target_gas, context)),
-            // nor Ethereum precompiles :
-            a if a == hash(1024) => Some(Sha3FIPS256::execute(input, target_gas, context)),
-            a if a == hash(1025) => Some(Dispatch::<R>::execute(input, target_gas, context)),
-            a if a == hash(1026) => Some(ECRecoverPublicKey::execute(input, target_gas, context)),
-            // Astar precompiles (starts from 0x5000):
-            a if a == hash(20480) => Some(Staking::<R>::execute(input, target_gas, context)),
-            // Default
-            _ => None,
-        }
+precompile_set! {
+    /// The PrecompileSet installed in the Shiden runtime.
+    ///
+    /// The following distribution has been decided for the precompiles
+    /// 0-1023: Ethereum Mainnet Precompiles
+    /// 1024-2047 Precompiles that are not in Ethereum Mainnet
+    pub struct ShidenNetworkPrecompiles<R> {
+        // Ethereum precompiles:
+        1 => ECRecover,
+        2 => Sha256,
+        3 => Ripemd160,
+        4 => Identity,
+        5 => Modexp,
+        6 => Bn128Add,
+        7 => Bn128Mul,
+        8 => Bn128Pairing,
+        // Non-Ethereum precompiles:
+        1024 => Sha3FIPS256,
+        1025 => FilteredDispatch<R, crate::DispatchPrecompileFilter>,
+        1026 => ECRecoverPublicKey,
+        // Astar precompiles (starts from 0x5000):
+        20480 => Staking<R>,
+        20481 => BalancesErc20<R>,
+        20482 => Sr25519Verify,
+        20483 => Ed25519Verify,
+        20484 => SubstrateEcdsaVerify,
+        20485 => AccountMapping<R>,
     }
-}
-
-fn hash(a: u64) -> H160 {
-    H160::from_low_u64_be(a)
+    where
+        R: pallet_evm::Config
+            + pallet_session::Config
+            + pallet_collator_selection::Config
+            + pallet_collator_exit::Config
+            + pallet_precompile_balances_erc20::Config
+            + pallet_address_registry::Config,
+        <R::Call as Dispatchable>::Origin:
+            From<Option<R::AccountId>> + OriginTrait<Call = R::Call>,
+        crate::DispatchPrecompileFilter: Contains<R::Call>,
+        R::AccountId: AsRef<[u8; 32]> + From<[u8; 32]>,
+        pallet_precompile_staking::BalanceOf<R>: Into<sp_core::U256>,
+        pallet_precompile_balances_erc20::BalanceOf<R>:
+            Into<sp_core::U256> + TryFrom<sp_core::U256>,
+        R::Call: From<pallet_session::Call<R>>
+            + From<pallet_collator_selection::Call<R>>
+            + From<pallet_collator_exit::Call<R>>
+            + Dispatchable<PostInfo = PostDispatchInfo>
+            + GetDispatchInfo
+            + Decode,
 }